@@ -0,0 +1,46 @@
+//! Times the per-line solvers on large generated inputs. Run it once as is
+//! and once with `--features parallel` to compare the two builds.
+
+use advent_of_code_2023::*;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn per_line(c: &mut Criterion) {
+    let mut rng = gen::Rng::new(0);
+    let day1 = gen::day1::generate(
+        &mut rng,
+        &gen::day1::Options {
+            lines: 1_000_000,
+            ..Default::default()
+        },
+    );
+    let day2 = gen::day2::generate(
+        &mut rng,
+        &gen::day2::Options {
+            games: 200_000,
+            ..Default::default()
+        },
+    );
+    let day4 = gen::day4::generate(
+        &mut rng,
+        &gen::day4::Options {
+            cards: 200_000,
+            ..Default::default()
+        },
+    );
+
+    let mut group = c.benchmark_group("per_line");
+    group.sample_size(10);
+    group.bench_function("day1 part2, 1M lines", |b| {
+        b.iter(|| day1::part2::solve(&day1).unwrap())
+    });
+    group.bench_function("day2 part1, 200k games", |b| {
+        b.iter(|| day2::part1::solve(12, 13, 14, &day2).unwrap())
+    });
+    group.bench_function("day4 part1, 200k cards", |b| {
+        b.iter(|| day4::part1::solve(&day4).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, per_line);
+criterion_main!(benches);
@@ -0,0 +1,204 @@
+use crate::solvers::Solver;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Answer(String),
+    Error(String),
+    Panic(String),
+}
+
+#[derive(Debug)]
+pub struct Run {
+    pub path: PathBuf,
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+}
+
+/// Expands each argument into input files: directories give every file in
+/// them, and `*` or `?` in the last component match file names.
+pub fn expand(patterns: &[PathBuf]) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+    let mut paths = Vec::new();
+    for pattern in patterns {
+        let name = pattern
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        if pattern.is_dir() {
+            paths.extend(files_in(pattern, |_| true)?);
+        } else if name.contains(['*', '?']) {
+            let dir = match pattern.parent() {
+                Some(parent) if parent != Path::new("") => parent,
+                _ => Path::new("."),
+            };
+            let matched = files_in(dir, |file| glob_match(name.as_bytes(), file.as_bytes()))?;
+            if matched.is_empty() {
+                return Err(format!("{} matches no files", pattern.display()).into());
+            }
+            paths.extend(matched);
+        } else {
+            paths.push(pattern.clone());
+        }
+    }
+    Ok(paths)
+}
+
+fn files_in<T>(dir: &Path, include: T) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>>
+where
+    T: Fn(&str) -> bool,
+{
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path.file_name().and_then(|name| name.to_str());
+        if path.is_file() && name.is_some_and(&include) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn glob_match(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some(b'*'), _) => {
+            glob_match(&pattern[1..], name) || (!name.is_empty() && glob_match(pattern, &name[1..]))
+        }
+        (Some(b'?'), Some(_)) => glob_match(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) if p == n => glob_match(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+/// Solves every input on a pool of threads, keeping the order of `paths`.
+/// A panicking solver is caught and reported against its own input only.
+pub fn solve_all(solver: Solver, paths: &[PathBuf]) -> Vec<Run> {
+    let next = AtomicUsize::new(0);
+    let runs = Mutex::new(Vec::with_capacity(paths.len()));
+    let workers = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(paths.len());
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(path) = paths.get(index) else {
+                    break;
+                };
+                let run = solve_one(solver, path);
+                runs.lock().expect("no worker panics").push((index, run));
+            });
+        }
+    });
+    let mut runs = runs.into_inner().expect("no worker panics");
+    runs.sort_by_key(|(index, _)| *index);
+    runs.into_iter().map(|(_, run)| run).collect()
+}
+
+fn solve_one(solver: Solver, path: &Path) -> Run {
+    let start = Instant::now();
+    let outcome = match std::fs::read_to_string(path) {
+        Err(e) => Outcome::Error(e.to_string()),
+        Ok(input) => match std::panic::catch_unwind(|| solver(&input)) {
+            Ok(Ok(answer)) => Outcome::Answer(answer.to_string()),
+            Ok(Err(e)) => Outcome::Error(e.to_string()),
+            Err(payload) => Outcome::Panic(
+                payload
+                    .downcast_ref::<&str>()
+                    .map(|message| message.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic".to_string()),
+            ),
+        },
+    };
+    Run {
+        path: path.to_path_buf(),
+        outcome,
+        elapsed: start.elapsed(),
+    }
+}
+
+/// One row per input with errors and panics in red, so they stand out.
+pub fn table(runs: &[Run]) -> String {
+    let rows = runs
+        .iter()
+        .map(|run| {
+            let (status, detail) = match &run.outcome {
+                Outcome::Answer(answer) => ("ok", answer.clone()),
+                Outcome::Error(e) => ("error", e.clone()),
+                Outcome::Panic(message) => ("panic", message.clone()),
+            };
+            (
+                run.path.display().to_string(),
+                status,
+                detail,
+                format!("{:.1?}", run.elapsed),
+            )
+        })
+        .collect::<Vec<_>>();
+    let width = rows
+        .iter()
+        .map(|row| row.0.len())
+        .chain(["file".len()])
+        .max()
+        .unwrap_or_default();
+    let mut output = format!(
+        "{:width$}  {:6}  {:>10}  answer\n",
+        "file", "status", "time"
+    );
+    for (file, status, detail, elapsed) in rows {
+        let line = format!("{file:width$}  {status:6}  {elapsed:>10}  {detail}");
+        if status == "ok" {
+            output.push_str(&line);
+        } else {
+            output.push_str(&format!("\x1b[1;31m{line}\x1b[0m"));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob() {
+        assert!(glob_match(b"4-*.txt", b"4-alice.txt"));
+        assert!(glob_match(b"4-?.txt", b"4-1.txt"));
+        assert!(!glob_match(b"4-?.txt", b"4-12.txt"));
+        assert!(!glob_match(b"*.txt", b"4-1.expected"));
+        let paths = expand(&[crate::examples::dir().join("1-*.txt")]).unwrap();
+        assert_eq!(
+            paths
+                .iter()
+                .map(|path| path.file_name().unwrap().to_str().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["1-1.txt", "1-2.txt"]
+        );
+    }
+
+    #[test]
+    fn errors_and_panics_stay_per_file() {
+        let paths = expand(&[crate::examples::dir().join("1-*.txt")]).unwrap();
+        let runs = solve_all(|input| Ok(crate::day1::part1::solve(input)?.into()), &paths);
+        assert_eq!(runs[0].outcome, Outcome::Answer("142".to_string()));
+        assert_eq!(
+            runs[1].outcome,
+            Outcome::Error("Error on line 1: no digits".to_string())
+        );
+        let runs = solve_all(
+            |input| match input.len() {
+                0..100 => panic!("too short"),
+                n => Ok((n as u64).into()),
+            },
+            &paths,
+        );
+        assert!(matches!(runs[0].outcome, Outcome::Panic(ref m) if m == "too short"));
+        assert!(table(&runs).contains("\x1b[1;31m"));
+    }
+}
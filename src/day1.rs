@@ -1,67 +1,106 @@
-fn format_results(results: impl Iterator<Item = Result<u64, &'static str>>) -> Result<u64, String> {
-    results
+use crate::num::Number;
+use crate::observe::{Event, Observer};
+
+fn format_results<N: Number>(values: Result<Vec<u64>, String>) -> Result<N, String> {
+    values?
+        .into_iter()
         .enumerate()
-        .fold(Ok(0), |acc, (line_number, result)| match acc {
-            Ok(total) => match result {
-                Ok(n) => Ok(total + n),
-                Err(e) => Err(format!("Error on line {line_number}: {e}")),
-            },
-            e => e,
+        .try_fold(N::zero(), |total, (line_number, n)| {
+            N::from_u64(n)
+                .and_then(|n| total.checked_add(&n))
+                .ok_or_else(|| format!("Error on line {line_number}: total overflows"))
         })
 }
 
+/// A digit found in a line, at the byte column where it starts.
+#[derive(Debug, Clone, Copy)]
+struct Found {
+    column: usize,
+    digit: u64,
+    spelled: bool,
+}
+
+/// Runs `extract` on every line and combines the first and last digits it
+/// finds, reporting both to `observer` and naming the line in any error.
+fn extract_lines<'a, F, I, O>(input: &'a str, extract: F, observer: &O) -> Result<Vec<u64>, String>
+where
+    F: Fn(&'a str) -> I + Send + Sync,
+    I: Iterator<Item = Found>,
+    O: Observer + ?Sized,
+{
+    crate::par::map_lines(input, |line_number, line| {
+        let mut digits = extract(line);
+        let first = digits
+            .next()
+            .ok_or_else(|| format!("Error on line {line_number}: no digits"))?;
+        let last = digits.last().unwrap_or(first);
+        for found in [first, last] {
+            observer.event(Event::Digit {
+                line: line_number,
+                column: found.column,
+                digit: found.digit,
+                spelled: found.spelled,
+            });
+        }
+        Ok(first.digit * 10 + last.digit)
+    })
+}
+
 pub mod part2 {
     use super::*;
 
     pub fn solve(input: &str) -> Result<u64, String> {
-        format_results(extract_data(input))
+        solve_as(input)
     }
 
-    fn extract_data<'a>(input: &'a str) -> impl Iterator<Item = Result<u64, &'static str>> + 'a {
-        input.lines().map(|line| {
-            let mut digits = Parser(line);
-            let first = digits.next().ok_or("no digits")?;
-            let last = digits.last().unwrap_or(first);
-            Ok(format!("{first}{last}")
-                .parse()
-                .expect("both are ascii digits"))
-        })
+    pub fn solve_as<N: Number>(input: &str) -> Result<N, String> {
+        format_results(extract_data(input, &()))
+    }
+
+    pub fn solve_observed<O: Observer + ?Sized>(input: &str, observer: &O) -> Result<u64, String> {
+        format_results(extract_data(input, observer))
+    }
+
+    fn extract_data<O: Observer + ?Sized>(input: &str, observer: &O) -> Result<Vec<u64>, String> {
+        extract_lines(input, |line| Parser { line, column: 0 }, observer)
     }
 
-    struct Parser<'a>(&'a str);
+    const WORDS: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+
+    /// Finds digits written as `1`-`9` or spelled out. A spelled digit is
+    /// consumed whole, so in `eightwo` only the `eight` is found.
+    struct Parser<'a> {
+        line: &'a str,
+        column: usize,
+    }
 
-    impl<'a> Iterator for Parser<'a> {
-        type Item = &'a str;
+    impl Iterator for Parser<'_> {
+        type Item = Found;
 
         fn next(&mut self) -> Option<Self::Item> {
-            fn try_parse(input: &mut Parser, item: &'static str) -> bool {
-                if input.0.starts_with(item) {
-                    input.0 = &input.0[item.len()..];
-                    true
-                } else {
-                    false
-                }
-            }
             loop {
-                if self.0.len() == 0 {
-                    return None;
-                } else {
-                    match self {
-                        _ if try_parse(self, "one") | try_parse(self, "1") => break Some("1"),
-                        _ if try_parse(self, "two") | try_parse(self, "2") => break Some("2"),
-                        _ if try_parse(self, "three") | try_parse(self, "3") => break Some("3"),
-                        _ if try_parse(self, "four") | try_parse(self, "4") => break Some("4"),
-                        _ if try_parse(self, "five") | try_parse(self, "5") => break Some("5"),
-                        _ if try_parse(self, "six") | try_parse(self, "6") => break Some("6"),
-                        _ if try_parse(self, "seven") | try_parse(self, "7") => break Some("7"),
-                        _ if try_parse(self, "eight") | try_parse(self, "8") => break Some("8"),
-                        _ if try_parse(self, "nine") | try_parse(self, "9") => break Some("9"),
-                        _ => {
-                            self.0 = &self.0[1..];
-                            continue;
-                        }
-                    }
+                let column = self.column;
+                let rest = &self.line[column..];
+                let c = rest.chars().next()?;
+                if let Some(digit) = c.to_digit(10).filter(|&digit| digit != 0) {
+                    self.column += 1;
+                    break Some(Found {
+                        column,
+                        digit: digit.into(),
+                        spelled: false,
+                    });
                 }
+                if let Some(i) = WORDS.iter().position(|word| rest.starts_with(word)) {
+                    self.column += WORDS[i].len();
+                    break Some(Found {
+                        column,
+                        digit: i as u64 + 1,
+                        spelled: true,
+                    });
+                }
+                self.column += c.len_utf8();
             }
         }
     }
@@ -69,22 +108,19 @@ pub mod part2 {
     #[cfg(test)]
     mod tests {
         use super::*;
-        const EXAMPLE_1: &'static str = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
-        const EXAMPLE_2: &'static str = "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen";
+        const EXAMPLE_1: &str = include_str!("../data/examples/1-1.txt");
+        const EXAMPLE_2: &str = include_str!("../data/examples/1-2.txt");
 
         #[test]
         fn extract_example_1() {
-            assert_eq!(
-                extract_data(EXAMPLE_1).collect::<Vec<_>>(),
-                vec![Ok(12), Ok(38), Ok(15), Ok(77)]
-            );
+            assert_eq!(extract_data(EXAMPLE_1, &()), Ok(vec![12, 38, 15, 77]));
         }
 
         #[test]
         fn extract_example_2() {
             assert_eq!(
-                extract_data(EXAMPLE_2).collect::<Vec<_>>(),
-                vec![Ok(29), Ok(83), Ok(13), Ok(24), Ok(42), Ok(14), Ok(76)]
+                extract_data(EXAMPLE_2, &()),
+                Ok(vec![29, 83, 13, 24, 42, 14, 76])
             );
         }
 
@@ -97,6 +133,19 @@ pub mod part2 {
         fn solve_example_2() {
             assert_eq!(solve(EXAMPLE_2), Ok(281));
         }
+
+        #[test]
+        fn overlapping_words() {
+            let trace = crate::observe::Trace::default();
+            assert_eq!(solve_observed("eightwo", &trace), Ok(88));
+            let eight = Event::Digit {
+                line: 0,
+                column: 0,
+                digit: 8,
+                spelled: true,
+            };
+            assert_eq!(trace.events(), vec![eight, eight]);
+        }
     }
 }
 
@@ -104,36 +153,56 @@ pub mod part1 {
     use super::*;
 
     pub fn solve(input: &str) -> Result<u64, String> {
-        format_results(extract_data(input))
+        solve_as(input)
     }
 
-    fn extract_data<'a>(input: &'a str) -> impl Iterator<Item = Result<u64, &'static str>> + 'a {
-        input.lines().map(|line| {
-            let mut digits = line.chars().filter(char::is_ascii_digit);
-            let first = digits.next().ok_or("no digits")?;
-            let last = digits.last().unwrap_or(first);
-            Ok(format!("{first}{last}")
-                .parse()
-                .expect("both are ascii digits"))
-        })
+    pub fn solve_as<N: Number>(input: &str) -> Result<N, String> {
+        format_results(extract_data(input, &()))
+    }
+
+    pub fn solve_observed<O: Observer + ?Sized>(input: &str, observer: &O) -> Result<u64, String> {
+        format_results(extract_data(input, observer))
+    }
+
+    fn extract_data<O: Observer + ?Sized>(input: &str, observer: &O) -> Result<Vec<u64>, String> {
+        extract_lines(
+            input,
+            |line| {
+                line.char_indices().filter_map(|(column, c)| {
+                    Some(Found {
+                        column,
+                        digit: c.to_digit(10)?.into(),
+                        spelled: false,
+                    })
+                })
+            },
+            observer,
+        )
     }
 
     #[cfg(test)]
     mod tests {
         use super::*;
-        const EXAMPLE_1: &'static str = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
+        const EXAMPLE_1: &str = include_str!("../data/examples/1-1.txt");
 
         #[test]
         fn extract_example_1() {
-            assert_eq!(
-                extract_data(EXAMPLE_1).collect::<Vec<_>>(),
-                vec![Ok(12), Ok(38), Ok(15), Ok(77)]
-            );
+            assert_eq!(extract_data(EXAMPLE_1, &()), Ok(vec![12, 38, 15, 77]));
         }
 
         #[test]
         fn solve_example_1() {
             assert_eq!(solve(EXAMPLE_1), Ok(142));
         }
+
+        #[test]
+        fn overflow() {
+            let values = Ok(vec![u64::MAX - 1, 1, 1]);
+            assert_eq!(
+                format_results::<u64>(values),
+                Err("Error on line 2: total overflows".to_string())
+            );
+            assert_eq!(solve_as::<i64>(EXAMPLE_1), Ok(142));
+        }
     }
 }
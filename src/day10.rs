@@ -0,0 +1,289 @@
+use crate::grid::{Direction, Grid, Node};
+use std::collections::HashSet;
+
+const PIPES: &[u8] = b"|-LJ7F";
+
+fn connections(pipe: u8) -> Option<[Direction; 2]> {
+    use Direction::*;
+    match pipe {
+        b'|' => Some([North, South]),
+        b'-' => Some([East, West]),
+        b'L' => Some([North, East]),
+        b'J' => Some([North, West]),
+        b'7' => Some([South, West]),
+        b'F' => Some([South, East]),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+struct Maze {
+    /// The input with `S` replaced by the pipe it hides.
+    grid: Grid,
+    /// The main loop in walking order, starting at `S`.
+    path: Vec<Node>,
+}
+
+impl Maze {
+    fn parse(input: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut grid = Grid::parse(input)?;
+        let start = grid.find(b'S').ok_or("no S in the maze")?;
+        let exits = Direction::ALL
+            .into_iter()
+            .filter(|direction| {
+                grid.get(start.step(*direction))
+                    .and_then(connections)
+                    .is_some_and(|pipe| pipe.contains(&direction.opposite()))
+            })
+            .collect::<Vec<_>>();
+        if exits.len() < 2 {
+            return Err(format!("S connects to {} pipes, expected 2", exits.len()).into());
+        }
+        // pipes that merely point at S can add exits, so S is whichever pair
+        // of them the loop actually leaves and comes back through
+        let mut error = None;
+        for pipe in PIPES.iter().copied() {
+            let Some(pair) = connections(pipe) else {
+                continue;
+            };
+            if !pair.iter().all(|direction| exits.contains(direction)) {
+                continue;
+            }
+            grid.set(start, pipe);
+            match Self::walk(&grid, start) {
+                Ok(path) => return Ok(Self { grid, path }),
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            }
+        }
+        Err(error.expect("any two exits make a pipe").into())
+    }
+
+    /// Follows the loop from `start` out of its first connection, and
+    /// checks that it comes back through the other.
+    fn walk(grid: &Grid, start: Node) -> Result<Vec<Node>, String> {
+        let [exit, entry] = grid
+            .get(start)
+            .and_then(connections)
+            .expect("start is a pipe");
+        let mut path = vec![start];
+        let mut direction = exit;
+        let mut node = start.step(direction);
+        while node != start {
+            let [a, b] = grid
+                .get(node)
+                .and_then(connections)
+                .filter(|pipe| pipe.contains(&direction.opposite()))
+                .ok_or_else(|| format!("the loop breaks at row {}, column {}", node.0, node.1))?;
+            direction = if a == direction.opposite() { b } else { a };
+            path.push(node);
+            node = node.step(direction);
+        }
+        if direction.opposite() != entry {
+            return Err("the loop does not close through S".to_string());
+        }
+        Ok(path)
+    }
+
+    /// Tiles inside the loop by scanline parity: moving along a row, the
+    /// inside flips at every loop tile with a connection to the north.
+    fn interior(&self) -> HashSet<Node> {
+        let on_loop = self.path.iter().copied().collect::<HashSet<_>>();
+        let mut interior = HashSet::new();
+        for i in 0..self.grid.height() as i64 {
+            let mut inside = false;
+            for j in 0..self.grid.width() as i64 {
+                let node = Node(i, j);
+                if on_loop.contains(&node) {
+                    let pipe = self.grid.get(node).and_then(connections);
+                    if pipe.is_some_and(|pipe| pipe.contains(&Direction::North)) {
+                        inside = !inside;
+                    }
+                } else if inside {
+                    interior.insert(node);
+                }
+            }
+        }
+        interior
+    }
+}
+
+pub mod render {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Format {
+        Plain,
+        Ansi,
+        Html,
+    }
+
+    /// Draws the loop with box-drawing characters, tiles it encloses as `I`
+    /// and everything else as `.`, as plain text, for a terminal or as a page.
+    pub fn render(input: &str, format: Format) -> Result<String, Box<dyn std::error::Error>> {
+        let maze = Maze::parse(input)?;
+        let on_loop = maze.path.iter().copied().collect::<HashSet<_>>();
+        let interior = maze.interior();
+        let mut output = String::new();
+        if format == Format::Html {
+            output.push_str(HTML_HEADER);
+        }
+        for i in 0..maze.grid.height() as i64 {
+            for j in 0..maze.grid.width() as i64 {
+                let node = Node(i, j);
+                let (text, style, class) = if on_loop.contains(&node) {
+                    let text = match maze.grid.get(node) {
+                        Some(b'|') => '│',
+                        Some(b'-') => '─',
+                        Some(b'L') => '└',
+                        Some(b'J') => '┘',
+                        Some(b'7') => '┐',
+                        _ => '┌',
+                    };
+                    (text, "\x1b[1m", "loop")
+                } else if interior.contains(&node) {
+                    ('I', "\x1b[32m", "inside")
+                } else {
+                    ('.', "\x1b[2m", "outside")
+                };
+                match format {
+                    Format::Plain => output.push(text),
+                    Format::Ansi => output.push_str(&format!("{style}{text}\x1b[0m")),
+                    Format::Html => {
+                        output.push_str(&format!("<span class=\"{class}\">{text}</span>"))
+                    }
+                }
+            }
+            output.push('\n');
+        }
+        if format == Format::Html {
+            output.push_str(HTML_FOOTER);
+        }
+        Ok(output)
+    }
+
+    const HTML_HEADER: &str = "\
+<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>Pipe maze</title>
+<style>
+body { background: #1e1e1e; color: #808080; }
+.loop { color: #e0e0e0; font-weight: bold; }
+.inside { color: #4ec94e; }
+.outside { color: #505050; }
+</style>
+</head>
+<body>
+<pre>
+";
+
+    const HTML_FOOTER: &str = "</pre>\n</body>\n</html>\n";
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn render_example_1() {
+            let input = include_str!("../data/examples/10-1.txt");
+            assert_eq!(
+                render(input, Format::Plain).unwrap(),
+                ".....\n.┌─┐.\n.│I│.\n.└─┘.\n.....\n"
+            );
+            let html = render(input, Format::Html).unwrap();
+            assert!(html.starts_with("<!DOCTYPE html>"));
+            assert_eq!(html.matches("class=\"loop\"").count(), 8);
+            assert!(html.contains("<span class=\"inside\">I</span>"));
+        }
+    }
+}
+
+pub mod part2 {
+    use super::*;
+
+    /// The shoelace formula gives the area inside the loop's tile centres, and
+    /// Pick's theorem turns that into the number of whole tiles inside:
+    /// `area = interior + boundary / 2 - 1`.
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        let maze = Maze::parse(input)?;
+        let path = &maze.path;
+        let twice_area = path
+            .iter()
+            .zip(path.iter().cycle().skip(1))
+            .map(|(a, b)| a.0 * b.1 - b.0 * a.1)
+            .sum::<i64>()
+            .unsigned_abs();
+        Ok(twice_area / 2 + 1 - path.len() as u64 / 2)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn solve_examples() {
+            assert_eq!(solve(include_str!("../data/examples/10-3.txt")).unwrap(), 4);
+            assert_eq!(solve(include_str!("../data/examples/10-4.txt")).unwrap(), 8);
+        }
+
+        #[test]
+        fn scanline_agrees() {
+            for example in crate::examples::load(10).unwrap() {
+                let maze = Maze::parse(&example.input).unwrap();
+                assert_eq!(
+                    maze.interior().len() as u64,
+                    solve(&example.input).unwrap(),
+                    "{}",
+                    example.name
+                );
+            }
+        }
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        Ok(Maze::parse(input)?.path.len() as u64 / 2)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_2: &str = include_str!("../data/examples/10-2.txt");
+
+        #[test]
+        fn solve_example_2() {
+            assert_eq!(solve(EXAMPLE_2).unwrap(), 8);
+        }
+
+        #[test]
+        fn start_with_stray_neighbours() {
+            // the pipes above and left of S point into it but are not on the loop
+            let input = ".|...\n-S-7.\n.|.|.\n.L-J.";
+            let maze = Maze::parse(input).unwrap();
+            assert_eq!(maze.grid.get(maze.path[0]), Some(b'F'));
+            assert_eq!(solve(input).unwrap(), 4);
+            assert_eq!(part2::solve(input).unwrap(), 1);
+        }
+
+        #[test]
+        fn infers_start() {
+            let maze = Maze::parse(EXAMPLE_2).unwrap();
+            assert_eq!(maze.grid.get(maze.path[0]), Some(b'F'));
+            assert_eq!(
+                Maze::parse("S-7\n|.|\nL-.").unwrap_err().to_string(),
+                "the loop breaks at row 2, column 2"
+            );
+            assert_eq!(
+                Maze::parse(".S.\n...").unwrap_err().to_string(),
+                "S connects to 0 pipes, expected 2"
+            );
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use crate::grid::{Grid, Node};
+
+/// Every coordinate along one axis after each empty line before it has grown
+/// to `factor` lines.
+fn expand(coordinates: &[i64], size: usize, factor: u64) -> Vec<u128> {
+    let mut occupied = vec![false; size];
+    for coordinate in coordinates {
+        occupied[*coordinate as usize] = true;
+    }
+    let mut position = Vec::with_capacity(size);
+    let mut next = 0u128;
+    for occupied in occupied {
+        position.push(next);
+        next += if occupied { 1 } else { factor as u128 };
+    }
+    coordinates
+        .iter()
+        .map(|coordinate| position[*coordinate as usize])
+        .collect()
+}
+
+/// Sum of `|a - b|` over all pairs: once sorted, the k-th value is larger
+/// than the k values before it, which a running total of them accounts for.
+fn pairwise_distance(mut values: Vec<u128>) -> u128 {
+    values.sort_unstable();
+    let mut before = 0;
+    let mut total = 0;
+    for (k, value) in values.into_iter().enumerate() {
+        total += value * k as u128 - before;
+        before += value;
+    }
+    total
+}
+
+/// Sum of the shortest distances between every pair of galaxies once each
+/// empty row and column is replaced by `factor` of them.
+pub fn solve_with(input: &str, factor: u64) -> Result<u64, Box<dyn std::error::Error>> {
+    if factor == 0 {
+        return Err("expansion factor must be at least 1".into());
+    }
+    let grid = Grid::parse(input)?;
+    let galaxies = grid
+        .nodes()
+        .filter(|node| grid.get(*node) == Some(b'#'))
+        .collect::<Vec<Node>>();
+    let rows = galaxies.iter().map(|node| node.0).collect::<Vec<_>>();
+    let columns = galaxies.iter().map(|node| node.1).collect::<Vec<_>>();
+    let total = pairwise_distance(expand(&rows, grid.height(), factor))
+        + pairwise_distance(expand(&columns, grid.width(), factor));
+    Ok(total
+        .try_into()
+        .map_err(|_| format!("the total distance {total} overflows"))?)
+}
+
+pub mod part2 {
+    use super::*;
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        solve_with(input, 1_000_000)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/11-1.txt");
+
+        #[test]
+        fn solve_example_1() {
+            assert_eq!(solve_with(EXAMPLE_1, 10).unwrap(), 1030);
+            assert_eq!(solve_with(EXAMPLE_1, 100).unwrap(), 8410);
+        }
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        solve_with(input, 2)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/11-1.txt");
+
+        #[test]
+        fn solve_example_1() {
+            assert_eq!(solve(EXAMPLE_1).unwrap(), 374);
+        }
+
+        #[test]
+        fn matches_pairs() {
+            let galaxies = grid_galaxies(EXAMPLE_1);
+            for factor in [1, 2, 7] {
+                let expanded = galaxies
+                    .iter()
+                    .map(|&(row, column)| {
+                        let empty_rows = (0..row).filter(|r| !galaxies.iter().any(|g| g.0 == *r));
+                        let empty_columns =
+                            (0..column).filter(|c| !galaxies.iter().any(|g| g.1 == *c));
+                        (
+                            row + empty_rows.count() as u64 * (factor - 1),
+                            column + empty_columns.count() as u64 * (factor - 1),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                let mut expected = 0;
+                for (i, a) in expanded.iter().enumerate() {
+                    for b in &expanded[i + 1..] {
+                        expected += a.0.abs_diff(b.0) + a.1.abs_diff(b.1);
+                    }
+                }
+                assert_eq!(solve_with(EXAMPLE_1, factor).unwrap(), expected);
+            }
+            assert!(solve_with(EXAMPLE_1, 0).is_err());
+        }
+
+        fn grid_galaxies(input: &str) -> Vec<(u64, u64)> {
+            input
+                .lines()
+                .enumerate()
+                .flat_map(|(i, line)| {
+                    line.match_indices('#')
+                        .map(move |(j, _)| (i as u64, j as u64))
+                })
+                .collect()
+        }
+    }
+}
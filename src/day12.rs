@@ -0,0 +1,205 @@
+/// Rows with more unknown springs than this are too slow to enumerate.
+pub const MAX_ENUMERATED_UNKNOWNS: usize = 20;
+
+/// A row of springs, `#` damaged, `.` operational and `?` unknown, with the
+/// lengths of its runs of damaged springs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    springs: Vec<u8>,
+    groups: Vec<usize>,
+}
+
+impl Row {
+    pub fn parse(input: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let (springs, groups) = input
+            .split_once(' ')
+            .ok_or("expected springs and group lengths separated by a space")?;
+        if let Some(c) = springs.chars().find(|c| !"#.?".contains(*c)) {
+            return Err(format!("invalid spring {c}").into());
+        }
+        let groups = groups
+            .trim()
+            .split(',')
+            .filter(|group| !group.is_empty())
+            .map(|group| group.parse())
+            .collect::<Result<Vec<_>, _>>()?;
+        if groups.contains(&0) {
+            return Err("group lengths must be at least 1".into());
+        }
+        Ok(Self {
+            springs: springs.as_bytes().to_vec(),
+            groups,
+        })
+    }
+
+    /// The row repeated `times` times, joined by unknown springs.
+    pub fn unfold(&self, times: usize) -> Self {
+        let mut springs = Vec::new();
+        for i in 0..times {
+            if i > 0 {
+                springs.push(b'?');
+            }
+            springs.extend(&self.springs);
+        }
+        Self {
+            springs,
+            groups: self.groups.repeat(times),
+        }
+    }
+
+    /// Number of ways to fill in the unknown springs, or `None` on overflow.
+    ///
+    /// `ways[i][g]` counts the fillings of `springs[i..]` that form exactly
+    /// `groups[g..]`, built from the end of the row backwards: a spring that
+    /// can be operational skips ahead one, and one that can start a damaged
+    /// run skips the run and the operational spring that must follow it.
+    pub fn arrangements(&self) -> Option<u64> {
+        let (n, m) = (self.springs.len(), self.groups.len());
+        // operational[i] counts the `.`s before i, to test a run in constant time
+        let mut operational = vec![0; n + 1];
+        for (i, spring) in self.springs.iter().enumerate() {
+            operational[i + 1] = operational[i] + usize::from(*spring == b'.');
+        }
+        let mut ways = vec![vec![0u64; m + 1]; n + 2];
+        ways[n][m] = 1;
+        ways[n + 1][m] = 1;
+        for i in (0..n).rev() {
+            for g in (0..=m).rev() {
+                let spring = self.springs[i];
+                let mut total = 0u64;
+                if spring != b'#' {
+                    total = ways[i + 1][g];
+                }
+                if spring != b'.' && g < m {
+                    let end = i + self.groups[g];
+                    if end <= n
+                        && operational[end] == operational[i]
+                        && self.springs.get(end) != Some(&b'#')
+                    {
+                        total = total.checked_add(ways[end + 1][g + 1])?;
+                    }
+                }
+                ways[i][g] = total;
+            }
+        }
+        Some(ways[0][0])
+    }
+
+    /// Every filling of the unknown springs that matches the groups, by
+    /// trying them all. Meant for checking [`Row::arrangements`] on small rows.
+    pub fn enumerate(&self) -> Result<Vec<String>, String> {
+        let unknowns = self
+            .springs
+            .iter()
+            .enumerate()
+            .filter(|(_, spring)| **spring == b'?')
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        if unknowns.len() > MAX_ENUMERATED_UNKNOWNS {
+            return Err(format!(
+                "{} unknown springs is too many to enumerate, the limit is {MAX_ENUMERATED_UNKNOWNS}",
+                unknowns.len()
+            ));
+        }
+        let mut arrangements = Vec::new();
+        for mask in 0..1u32 << unknowns.len() {
+            let mut springs = self.springs.clone();
+            for (bit, i) in unknowns.iter().enumerate() {
+                springs[*i] = if mask & (1 << bit) != 0 { b'#' } else { b'.' };
+            }
+            let groups = springs
+                .split(|spring| *spring == b'.')
+                .map(<[u8]>::len)
+                .filter(|len| *len > 0)
+                .collect::<Vec<_>>();
+            if groups == self.groups {
+                arrangements.push(String::from_utf8(springs).expect("springs are ascii"));
+            }
+        }
+        Ok(arrangements)
+    }
+}
+
+fn sum_arrangements(input: &str, folds: usize) -> Result<u64, Box<dyn std::error::Error>> {
+    crate::par::map_lines(input, |i, line| {
+        let row = Row::parse(line).map_err(|e| format!("line {i}: {e}"))?;
+        row.unfold(folds)
+            .arrangements()
+            .ok_or_else(|| format!("line {i}: arrangements overflow"))
+    })?
+    .into_iter()
+    .enumerate()
+    .try_fold(0u64, |acc, (i, ways)| {
+        acc.checked_add(ways)
+            .ok_or_else(|| format!("total overflows at line {i}").into())
+    })
+}
+
+pub mod part2 {
+    use super::*;
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        sum_arrangements(input, 5)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/12-1.txt");
+
+        #[test]
+        fn solve_example_1() {
+            assert_eq!(solve(EXAMPLE_1).unwrap(), 525152);
+        }
+
+        #[test]
+        fn unfold() {
+            let row = Row::parse(".# 1").unwrap().unfold(5);
+            assert_eq!(row, Row::parse(".#?.#?.#?.#?.# 1,1,1,1,1").unwrap());
+        }
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        sum_arrangements(input, 1)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/12-1.txt");
+
+        #[test]
+        fn solve_example_1() {
+            assert_eq!(solve(EXAMPLE_1).unwrap(), 21);
+        }
+
+        #[test]
+        fn enumerate() {
+            let row = Row::parse(".??..??...?##. 1,1,3").unwrap();
+            assert_eq!(
+                row.enumerate().unwrap(),
+                vec![
+                    ".#...#....###.",
+                    "..#..#....###.",
+                    ".#....#...###.",
+                    "..#...#...###.",
+                ]
+            );
+            for line in EXAMPLE_1.lines() {
+                let row = Row::parse(line).unwrap();
+                assert_eq!(
+                    row.arrangements(),
+                    Some(row.enumerate().unwrap().len() as u64)
+                );
+            }
+            let row = Row::parse(&format!("{} 1", "?".repeat(21))).unwrap();
+            assert!(row.enumerate().is_err());
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use crate::grid::Grid;
+
+/// A pattern of ash `.` and rock `#`, with each row and column packed into a
+/// bitmask so comparing two lines is an XOR and a popcount.
+#[derive(Debug)]
+struct Pattern {
+    rows: Vec<u64>,
+    columns: Vec<u64>,
+}
+
+impl Pattern {
+    fn parse(input: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let grid = Grid::parse(input)?;
+        if grid.width() > 64 || grid.height() > 64 {
+            return Err(format!(
+                "a {}x{} pattern is too large, the limit is 64x64",
+                grid.width(),
+                grid.height()
+            )
+            .into());
+        }
+        let mut rows = vec![0; grid.height()];
+        let mut columns = vec![0; grid.width()];
+        for node in grid.nodes() {
+            let (i, j) = (node.0 as usize, node.1 as usize);
+            match grid.get(node) {
+                Some(b'#') => {
+                    rows[i] |= 1 << j;
+                    columns[j] |= 1 << i;
+                }
+                Some(b'.') => {}
+                _ => return Err(format!("invalid cell at row {i}, column {j}").into()),
+            }
+        }
+        Ok(Self { rows, columns })
+    }
+
+    /// Value of the reflection line with exactly `smudges` differing cells:
+    /// the columns left of a vertical line, or 100 times the rows above a
+    /// horizontal one.
+    fn summarize(&self, smudges: u32) -> Option<u64> {
+        reflection(&self.columns, smudges)
+            .or_else(|| reflection(&self.rows, smudges).map(|above| above * 100))
+            .map(|value| value as u64)
+    }
+}
+
+/// The number of lines before a mirror between two lines, where the lines
+/// that reflect onto each other differ in exactly `smudges` cells in total.
+fn reflection(lines: &[u64], smudges: u32) -> Option<usize> {
+    (1..lines.len()).find(|&mirror| {
+        lines[..mirror]
+            .iter()
+            .rev()
+            .zip(&lines[mirror..])
+            .map(|(a, b)| (a ^ b).count_ones())
+            .sum::<u32>()
+            == smudges
+    })
+}
+
+/// Sum of every pattern's summary when each mirror has exactly `smudges`
+/// smudges on it.
+pub fn solve_with(input: &str, smudges: u32) -> Result<u64, Box<dyn std::error::Error>> {
+    input.split("\n\n").enumerate().try_fold(
+        0u64,
+        |acc, (i, pattern)| -> Result<u64, Box<dyn std::error::Error>> {
+            let pattern = Pattern::parse(pattern).map_err(|e| format!("pattern {i}: {e}"))?;
+            let value = pattern
+                .summarize(smudges)
+                .ok_or_else(|| format!("pattern {i} has no reflection with {smudges} smudges"))?;
+            Ok(acc
+                .checked_add(value)
+                .ok_or_else(|| format!("total overflows at pattern {i}"))?)
+        },
+    )
+}
+
+pub mod part2 {
+    use super::*;
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        solve_with(input, 1)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/13-1.txt");
+
+        #[test]
+        fn solve_example_1() {
+            assert_eq!(solve(EXAMPLE_1).unwrap(), 400);
+        }
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        solve_with(input, 0)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/13-1.txt");
+
+        #[test]
+        fn solve_example_1() {
+            assert_eq!(solve(EXAMPLE_1).unwrap(), 405);
+        }
+
+        #[test]
+        fn smudges() {
+            let pattern = Pattern::parse("#..\n#..\n.#.").unwrap();
+            assert_eq!(pattern.rows, vec![0b001, 0b001, 0b010]);
+            assert_eq!(pattern.columns, vec![0b011, 0b100, 0b000]);
+            assert_eq!(pattern.summarize(0), Some(100));
+            assert_eq!(pattern.summarize(1), Some(2));
+            assert_eq!(pattern.summarize(2), Some(200));
+            assert_eq!(pattern.summarize(3), Some(1));
+            assert_eq!(
+                solve_with("#.\n.#\n\n##\n#.", 3).unwrap_err().to_string(),
+                "pattern 0 has no reflection with 3 smudges"
+            );
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::grid::{Direction, Grid, Node};
+use std::collections::HashMap;
+
+/// The platform of round rocks `O`, cube rocks `#` and empty space `.`.
+fn parse(input: &str) -> Result<Grid, Box<dyn std::error::Error>> {
+    let grid = Grid::parse(input)?;
+    if let Some(node) = grid
+        .nodes()
+        .find(|node| !matches!(grid.get(*node), Some(b'O' | b'#' | b'.')))
+    {
+        return Err(format!("invalid cell at row {}, column {}", node.0, node.1).into());
+    }
+    Ok(grid)
+}
+
+/// Rolls every round rock as far as it goes towards `direction`. Each line
+/// is walked away from the edge the rocks roll to, keeping the nearest free
+/// cell that the next round rock lands on.
+pub fn tilt(grid: &mut Grid, direction: Direction) {
+    let (height, width) = (grid.height() as i64, grid.width() as i64);
+    let starts = match direction {
+        Direction::North => (0..width).map(|j| Node(0, j)).collect::<Vec<_>>(),
+        Direction::South => (0..width).map(|j| Node(height - 1, j)).collect(),
+        Direction::West => (0..height).map(|i| Node(i, 0)).collect(),
+        Direction::East => (0..height).map(|i| Node(i, width - 1)).collect(),
+    };
+    let back = direction.opposite();
+    for start in starts {
+        let mut free = start;
+        let mut node = start;
+        while let Some(cell) = grid.get(node) {
+            match cell {
+                b'#' => free = node.step(back),
+                b'O' => {
+                    grid.set(node, b'.');
+                    grid.set(free, b'O');
+                    free = free.step(back);
+                }
+                _ => {}
+            }
+            node = node.step(back);
+        }
+    }
+}
+
+/// Tilts north, west, south and then east.
+pub fn cycle(grid: &mut Grid) {
+    for direction in [
+        Direction::North,
+        Direction::West,
+        Direction::South,
+        Direction::East,
+    ] {
+        tilt(grid, direction);
+    }
+}
+
+/// The platform after `cycles` spin cycles. Once a state repeats the cycles
+/// between its two sightings loop forever, so the rest are skipped.
+pub fn spin(input: &str, cycles: u64) -> Result<Grid, Box<dyn std::error::Error>> {
+    let mut grid = parse(input)?;
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    for n in 0..cycles {
+        if let Some(start) = seen.insert(grid.clone(), n) {
+            let period = n - start;
+            return Ok(history.swap_remove((start + (cycles - start) % period) as usize));
+        }
+        history.push(grid.clone());
+        cycle(&mut grid);
+    }
+    Ok(grid)
+}
+
+/// Each round rock weighs the number of rows from it to the south edge.
+fn north_load(grid: &Grid) -> u64 {
+    grid.nodes()
+        .filter(|node| grid.get(*node) == Some(b'O'))
+        .map(|node| (grid.height() as i64 - node.0) as u64)
+        .sum()
+}
+
+pub mod part2 {
+    use super::*;
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        Ok(north_load(&spin(input, 1_000_000_000)?))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/14-1.txt");
+
+        #[test]
+        fn solve_example_1() {
+            assert_eq!(solve(EXAMPLE_1).unwrap(), 64);
+        }
+
+        #[test]
+        fn spin_example_1() {
+            assert_eq!(
+                spin(EXAMPLE_1, 3).unwrap().to_string(),
+                ".....#....\n\
+                 ....#...O#\n\
+                 .....##...\n\
+                 ..O#......\n\
+                 .....OOO#.\n\
+                 .O#...O#.#\n\
+                 ....O#...O\n\
+                 .......OOO\n\
+                 #...O###.O\n\
+                 #.OOO#...O\n"
+            );
+            let mut grid = parse(EXAMPLE_1).unwrap();
+            for cycles in 0..30 {
+                assert_eq!(spin(EXAMPLE_1, cycles).unwrap(), grid);
+                cycle(&mut grid);
+            }
+        }
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        let mut grid = parse(input)?;
+        tilt(&mut grid, Direction::North);
+        Ok(north_load(&grid))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/14-1.txt");
+
+        #[test]
+        fn solve_example_1() {
+            assert_eq!(solve(EXAMPLE_1).unwrap(), 136);
+        }
+
+        #[test]
+        fn tilt_each_way() {
+            let mut grid = parse("O.#O.\n.O..O").unwrap();
+            tilt(&mut grid, Direction::East);
+            assert_eq!(grid.to_string(), ".O#.O\n...OO\n");
+            tilt(&mut grid, Direction::South);
+            assert_eq!(grid.to_string(), "..#.O\n.O.OO\n");
+            tilt(&mut grid, Direction::West);
+            assert_eq!(grid.to_string(), "..#O.\nOOO..\n");
+            tilt(&mut grid, Direction::North);
+            assert_eq!(grid.to_string(), "OO#O.\n..O..\n");
+            assert!(parse("O.x").is_err());
+        }
+    }
+}
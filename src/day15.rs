@@ -0,0 +1,144 @@
+/// The Holiday ASCII String Helper: for each byte add it, multiply by 17
+/// and keep the remainder modulo 256.
+pub fn hash(s: &str) -> u8 {
+    s.bytes()
+        .fold(0u8, |value, byte| value.wrapping_add(byte).wrapping_mul(17))
+}
+
+fn steps(input: &str) -> impl Iterator<Item = &str> {
+    input.trim().split(',')
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lens {
+    pub label: String,
+    pub focal_length: u8,
+}
+
+/// The 256 boxes of lenses, each kept in the order its lenses went in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Boxes(Vec<Vec<Lens>>);
+
+impl Default for Boxes {
+    fn default() -> Self {
+        Self(vec![Vec::new(); 256])
+    }
+}
+
+impl Boxes {
+    /// The lenses in box `index`, front to back.
+    pub fn get(&self, index: u8) -> &[Lens] {
+        &self.0[index as usize]
+    }
+
+    /// Runs one step: `label=n` replaces the lens with that label in place or
+    /// adds one at the back, and `label-` takes it out.
+    pub fn apply(&mut self, step: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(label) = step.strip_suffix('-') {
+            self.0[hash(label) as usize].retain(|lens| lens.label != label);
+            return Ok(());
+        }
+        let (label, focal_length) = step
+            .split_once('=')
+            .ok_or_else(|| format!("step {step} is neither an = nor a -"))?;
+        let focal_length = focal_length
+            .parse()
+            .map_err(|e| format!("step {step}: {e}"))?;
+        let lenses = &mut self.0[hash(label) as usize];
+        match lenses.iter_mut().find(|lens| lens.label == label) {
+            Some(lens) => lens.focal_length = focal_length,
+            None => lenses.push(Lens {
+                label: label.to_string(),
+                focal_length,
+            }),
+        }
+        Ok(())
+    }
+
+    /// Sum over every lens of its box number plus one, times its slot plus
+    /// one, times its focal length.
+    pub fn focusing_power(&self) -> u64 {
+        self.0
+            .iter()
+            .enumerate()
+            .flat_map(|(i, lenses)| {
+                lenses.iter().enumerate().map(move |(slot, lens)| {
+                    (i as u64 + 1) * (slot as u64 + 1) * lens.focal_length as u64
+                })
+            })
+            .sum()
+    }
+}
+
+pub mod part2 {
+    use super::*;
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        let mut boxes = Boxes::default();
+        for step in steps(input) {
+            boxes.apply(step)?;
+        }
+        Ok(boxes.focusing_power())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/15-1.txt");
+
+        #[test]
+        fn solve_example_1() {
+            assert_eq!(solve(EXAMPLE_1).unwrap(), 145);
+        }
+
+        #[test]
+        fn boxes_keep_order() {
+            let mut boxes = Boxes::default();
+            for step in "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7".split(',') {
+                boxes.apply(step).unwrap();
+            }
+            let lens = |label: &str, focal_length| Lens {
+                label: label.to_string(),
+                focal_length,
+            };
+            assert_eq!(boxes.get(0), [lens("rn", 1), lens("cm", 2)]);
+            assert_eq!(boxes.get(1), []);
+            assert_eq!(boxes.get(3), [lens("ot", 7), lens("ab", 5), lens("pc", 6)]);
+            assert_eq!(
+                boxes.apply("ab").unwrap_err().to_string(),
+                "step ab is neither an = nor a -"
+            );
+            assert!(boxes.apply("ab=x").is_err());
+        }
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        Ok(steps(input).map(|step| hash(step) as u64).sum())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/15-1.txt");
+
+        #[test]
+        fn solve_example_1() {
+            assert_eq!(solve(EXAMPLE_1).unwrap(), 1320);
+        }
+
+        #[test]
+        fn hash_examples() {
+            assert_eq!(hash("HASH"), 52);
+            assert_eq!(hash("rn=1"), 30);
+            assert_eq!(hash("rn"), 0);
+            assert_eq!(hash("qp"), 1);
+            assert_eq!(hash(""), 0);
+        }
+    }
+}
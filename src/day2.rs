@@ -1,4 +1,7 @@
-#[derive(Default, Debug, PartialEq, Eq)]
+use crate::num::Number;
+use crate::observe::{Event, Observer};
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
 struct Round {
     r: u64,
     g: u64,
@@ -7,23 +10,25 @@ struct Round {
 
 impl Round {
     fn parse(input: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        input.split(",").fold(Ok(Round::default()), |acc, curr| {
-            let Ok(mut acc) = acc else {
-                return acc;
-            };
-            let (count, colour) = curr
-                .trim()
-                .split_once(" ")
-                .ok_or("must contain colour and number separated by space")?;
-            let count = count.trim().parse::<u64>()?;
-            match colour.trim() {
-                "red" => acc.r += count,
-                "green" => acc.g += count,
-                "blue" => acc.b += count,
-                other => return Err(format!("invalid colour: {other}").into()),
-            };
-            Ok(acc)
-        })
+        input
+            .split(",")
+            .try_fold(Round::default(), |mut acc, curr| {
+                let (count, colour) = curr
+                    .trim()
+                    .split_once(" ")
+                    .ok_or("must contain colour and number separated by space")?;
+                let count = count.trim().parse::<u64>()?;
+                let total = match colour.trim() {
+                    "red" => &mut acc.r,
+                    "green" => &mut acc.g,
+                    "blue" => &mut acc.b,
+                    other => return Err(format!("invalid colour: {other}").into()),
+                };
+                *total = total
+                    .checked_add(count)
+                    .ok_or_else(|| format!("too many {} cubes", colour.trim()))?;
+                Ok(acc)
+            })
     }
 
     fn max(&self, other: Self) -> Self {
@@ -34,8 +39,12 @@ impl Round {
         }
     }
 
-    fn power_of_cubes(&self) -> u64 {
-        self.r * self.g * self.b
+    fn power_of_cubes<N: Number>(&self) -> Option<N> {
+        [self.g, self.b]
+            .into_iter()
+            .try_fold(N::from_u64(self.r)?, |power, n| {
+                power.checked_mul(&N::from_u64(n)?)
+            })
     }
 }
 
@@ -48,14 +57,14 @@ impl<'a> Iterator for Rounds<'a> {
     type Item = Result<Round, Box<dyn std::error::Error>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.rest.len() == 0 {
+        if self.rest.is_empty() {
             return None;
         }
         let round = if let Some((curr, rest)) = self.rest.split_once(";") {
             self.rest = rest;
             curr
         } else {
-            std::mem::replace(&mut self.rest, "")
+            std::mem::take(&mut self.rest)
         };
         Some(Round::parse(round))
     }
@@ -77,7 +86,7 @@ impl<'a> Game<'a> {
         }
     }
 
-    fn rounds(&self) -> Rounds {
+    fn rounds(&self) -> Rounds<'_> {
         Rounds {
             rest: self.rounds_data,
         }
@@ -85,22 +94,81 @@ impl<'a> Game<'a> {
 
     fn minium_cubes(&self) -> Result<Round, Box<dyn std::error::Error>> {
         self.rounds()
-            .fold(Ok(Round::default()), |min_round, round| {
-                Ok(min_round?.max(round?))
+            .try_fold(Round::default(), |min_round, round| {
+                Ok(min_round.max(round?))
             })
     }
 }
 
+pub mod repl {
+    use super::*;
+    use crate::repl::{arg, unknown};
+
+    /// Every game's rounds, parsed up front.
+    #[derive(Debug)]
+    pub struct Games(Vec<(u64, Vec<Round>)>);
+
+    impl Games {
+        pub fn parse(input: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            input
+                .lines()
+                .map(|line| {
+                    let game = Game::parse(line);
+                    Ok((game.id, game.rounds().collect::<Result<_, _>>()?))
+                })
+                .collect::<Result<_, _>>()
+                .map(Self)
+        }
+    }
+
+    fn describe(round: &Round) -> String {
+        format!("{} red, {} green, {} blue", round.r, round.g, round.b)
+    }
+
+    impl crate::repl::Model for Games {
+        fn help(&self) -> &'static str {
+            "game <id> min|rounds"
+        }
+
+        fn query(&self, words: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+            let ["game", id, detail] = words else {
+                return Err(unknown(words, self.help()));
+            };
+            let id = arg::<u64>(id, "game id")?;
+            let (_, rounds) = self
+                .0
+                .iter()
+                .find(|(game, _)| *game == id)
+                .ok_or_else(|| format!("no game {id}"))?;
+            match *detail {
+                "min" => {
+                    let min = rounds
+                        .iter()
+                        .fold(Round::default(), |min, round| min.max(round.clone()));
+                    let power = min.power_of_cubes::<u64>().ok_or("power overflows")?;
+                    Ok(format!("{} (power {power})", describe(&min)))
+                }
+                "rounds" => Ok(rounds.iter().map(describe).collect::<Vec<_>>().join("\n")),
+                _ => Err(unknown(words, self.help())),
+            }
+        }
+    }
+}
+
 pub mod part2 {
     use super::*;
 
     pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
-        input.lines().fold(Ok(0), |acc, game| {
-            acc.and_then(|acc| {
-                Game::parse(game)
-                    .minium_cubes()
-                    .and_then(|round| Ok(round.power_of_cubes() + acc))
-            })
+        solve_as(input)
+    }
+
+    pub fn solve_as<N: Number>(input: &str) -> Result<N, Box<dyn std::error::Error>> {
+        input.lines().try_fold(N::zero(), |acc, game| {
+            let game = Game::parse(game);
+            game.minium_cubes()?
+                .power_of_cubes::<N>()
+                .and_then(|power| power.checked_add(&acc))
+                .ok_or_else(|| format!("Total overflows at game {}", game.id).into())
         })
     }
 
@@ -108,35 +176,59 @@ pub mod part2 {
     mod tests {
         use super::*;
 
-        const EXAMPLE_1: &str = "\
-                                 Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-                                 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-                                 Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-                                 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-                                 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green\
-                                 ";
+        const EXAMPLE_1: &str = include_str!("../data/examples/2-1.txt");
         #[test]
         fn solve_example_1() {
             assert_eq!(part2::solve(EXAMPLE_1).unwrap(), 2286);
         }
+
+        #[test]
+        fn overflow() {
+            let input =
+                "Game 1: 1 red, 1 green, 1 blue\nGame 2: 4294967296 red, 4294967296 green, 1 blue";
+            assert_eq!(
+                part2::solve(input).unwrap_err().to_string(),
+                "Total overflows at game 2"
+            );
+            assert_eq!(
+                Round::parse(&format!("{} red, 1 red", u64::MAX))
+                    .unwrap_err()
+                    .to_string(),
+                "too many red cubes"
+            );
+        }
     }
 }
 
 pub mod part1 {
     use super::*;
 
-    fn are_rounds_valid<T>(rule: T, rounds: Rounds) -> Result<bool, Box<dyn std::error::Error>>
+    fn are_rounds_valid<T, O>(
+        rule: T,
+        game: &Game,
+        observer: &O,
+    ) -> Result<bool, Box<dyn std::error::Error>>
     where
-        T: Fn(Round) -> bool,
+        T: Fn(&Round) -> bool,
+        O: Observer + ?Sized,
     {
-        rounds.fold(Ok(true), |acc, round| {
-            let round = round?;
-            if !rule(round) {
-                Ok(false)
-            } else {
-                acc
-            }
-        })
+        game.rounds()
+            .enumerate()
+            .try_fold(true, |valid, (i, round)| {
+                let round = round?;
+                if !rule(&round) {
+                    observer.event(Event::RoundRejected {
+                        game: game.id,
+                        round: i,
+                        red: round.r,
+                        green: round.g,
+                        blue: round.b,
+                    });
+                    Ok(false)
+                } else {
+                    Ok(valid)
+                }
+            })
     }
 
     pub fn solve(
@@ -144,26 +236,41 @@ pub mod part1 {
         green: u64,
         blue: u64,
         input: &str,
+    ) -> Result<u64, Box<dyn std::error::Error>> {
+        solve_observed(red, green, blue, input, &())
+    }
+
+    pub fn solve_observed<O: Observer + ?Sized>(
+        red: u64,
+        green: u64,
+        blue: u64,
+        input: &str,
+        observer: &O,
     ) -> Result<u64, Box<dyn std::error::Error>> {
         let counts = Round {
             r: red,
             g: green,
             b: blue,
         };
-        let rule = move |round: Round| match round {
+        let rule = move |round: &Round| match round {
             _ if counts.r < round.r => false,
             _ if counts.g < round.g => false,
             _ if counts.b < round.b => false,
             _ => true,
         };
-        input.lines().fold(Ok(0), |id_sum, game| {
-            let id_sum = id_sum?;
+        crate::par::map_lines(input, move |_, game| {
             let game = Game::parse(game);
-            if are_rounds_valid(rule, game.rounds())? {
-                Ok(id_sum + game.id)
-            } else {
-                Ok(id_sum)
+            match are_rounds_valid(rule, &game, observer) {
+                Ok(true) => Ok(game.id),
+                Ok(false) => Ok(0),
+                Err(e) => Err(e.to_string()),
             }
+        })?
+        .into_iter()
+        .try_fold(0u64, |id_sum, id| {
+            id_sum
+                .checked_add(id)
+                .ok_or_else(|| format!("Total overflows at game {id}").into())
         })
     }
 
@@ -171,13 +278,7 @@ pub mod part1 {
     mod tests {
         use super::*;
 
-        const EXAMPLE_1: &str = "\
-                                 Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-                                 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-                                 Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-                                 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-                                 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green\
-                                 ";
+        const EXAMPLE_1: &str = include_str!("../data/examples/2-1.txt");
 
         #[test]
         fn parse() {
@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 struct Node(i64, i64);
 
 impl Node {
@@ -6,17 +6,25 @@ impl Node {
         (self.0 == other.0 || other.0 == self.0 + 1 || other.0 == self.0 - 1)
             && (self.1 == other.1 || other.1 == self.1 + 1 || other.1 == (self.1 - 1))
     }
+
+    fn neighbours(&self) -> impl Iterator<Item = Node> + '_ {
+        (-1..=1)
+            .flat_map(|di| (-1..=1).map(move |dj| (di, dj)))
+            .filter(|&(di, dj)| di != 0 || dj != 0)
+            .map(|(di, dj)| Node(self.0 + di, self.1 + dj))
+    }
 }
 
 #[derive(Debug)]
 struct Symbol {
     node: Node,
+    kind: char,
 }
 
 #[derive(Debug)]
 struct Part {
     nodes: Vec<Node>,
-    value: u64,
+    value: i64,
 }
 
 impl Part {
@@ -28,6 +36,14 @@ impl Part {
     }
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Accept rows of differing widths instead of rejecting the schematic.
+    pub lenient: bool,
+    /// Read a '-' directly in front of a number as its sign rather than a symbol.
+    pub signed: bool,
+}
+
 #[derive(Debug, Default)]
 struct SchematicGraph {
     symbols: Vec<Symbol>,
@@ -35,129 +51,755 @@ struct SchematicGraph {
 }
 
 impl SchematicGraph {
-    fn parse<T>(input: &str, include_symbol: T) -> Self
+    /// Columns are counted in chars so that non-ASCII symbols occupy a single
+    /// cell, while numbers are sliced out of the line by byte offset.
+    fn parse<T>(
+        input: &str,
+        options: ParseOptions,
+        include_symbol: T,
+    ) -> Result<Self, Box<dyn std::error::Error>>
     where
         T: Fn(char) -> bool,
     {
         let mut graph = SchematicGraph::default();
+        let mut width = None;
         for (i, line) in input.lines().enumerate() {
-            let mut iter = line.chars().enumerate().peekable();
-            while let Some((j, c)) = iter.next() {
-                match c {
-                    '.' => continue,
-                    c if c.is_ascii_digit() => {
-                        let mut end_index = j;
-                        loop {
-                            if let Some(_) = iter.next_if(|(_, c_next)| c_next.is_ascii_digit()) {
-                                end_index += 1;
-                            } else {
-                                break;
-                            }
-                        }
-                        let value = *&line[j..=end_index].parse::<u64>().expect("is ascii digit");
-                        let nodes = (j..=end_index).map(|j| Node(i as i64, j as i64)).collect();
-                        graph.parts.push(Part { nodes, value });
+            let cells = line.char_indices().collect::<Vec<_>>();
+            match width {
+                None => width = Some(cells.len()),
+                Some(width) if width != cells.len() && !options.lenient => {
+                    return Err(
+                        format!("row {i} has {} columns, expected {width}", cells.len()).into(),
+                    )
+                }
+                _ => {}
+            }
+            let mut j = 0;
+            while j < cells.len() {
+                let (start, c) = cells[j];
+                let is_sign = options.signed
+                    && c == '-'
+                    && cells.get(j + 1).is_some_and(|(_, c)| c.is_ascii_digit());
+                if c.is_ascii_digit() || is_sign {
+                    let mut end = j + 1;
+                    while cells.get(end).is_some_and(|(_, c)| c.is_ascii_digit()) {
+                        end += 1;
                     }
-                    c if include_symbol(c) => graph.symbols.push(Symbol {
-                        node: Node(i as i64, j as i64),
-                    }),
-                    _ => {}
+                    let end_byte = cells.get(end).map_or(line.len(), |(byte, _)| *byte);
+                    let value = line[start..end_byte]
+                        .parse::<i64>()
+                        .map_err(|e| format!("row {i}, column {j}: {e}"))?;
+                    let nodes = (j..end).map(|j| Node(i as i64, j as i64)).collect();
+                    graph.parts.push(Part { nodes, value });
+                    j = end;
+                } else {
+                    if c != '.' && include_symbol(c) {
+                        graph.symbols.push(Symbol {
+                            node: Node(i as i64, j as i64),
+                            kind: c,
+                        });
+                    }
+                    j += 1;
                 }
             }
         }
-        graph
+        Ok(graph)
     }
 }
 
-pub mod part2 {
+pub mod render {
     use super::*;
+    use std::fmt::Write;
 
-    pub fn solve(input: &str) -> u64 {
-        let schematic = SchematicGraph::parse(input, |c| c == '*');
-        schematic
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Format {
+        Ansi,
+        Html,
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    enum Cell {
+        Empty,
+        Part(usize),
+        Symbol(usize),
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum SymbolKind {
+        Gear(i64, i64),
+        Lone,
+        Other,
+    }
+
+    /// Prints the schematic back out with every number marked as counted
+    /// (touching a symbol) or ignored, gears annotated with their two part
+    /// values and symbols that touch no part flagged as lone.
+    pub fn render(
+        input: &str,
+        options: ParseOptions,
+        format: Format,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let schematic = SchematicGraph::parse(input, options, |_| true)?;
+        let rows = input
+            .lines()
+            .map(|line| line.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut cells = rows
+            .iter()
+            .map(|row| vec![Cell::Empty; row.len()])
+            .collect::<Vec<_>>();
+        for (index, part) in schematic.parts.iter().enumerate() {
+            for node in &part.nodes {
+                cells[node.0 as usize][node.1 as usize] = Cell::Part(index);
+            }
+        }
+        for (index, symbol) in schematic.symbols.iter().enumerate() {
+            cells[symbol.node.0 as usize][symbol.node.1 as usize] = Cell::Symbol(index);
+        }
+        let counted = schematic
+            .parts
+            .iter()
+            .map(|part| {
+                schematic
+                    .symbols
+                    .iter()
+                    .any(|symbol| part.is_neighbour(&symbol.node))
+            })
+            .collect::<Vec<_>>();
+        let kinds = schematic
             .symbols
             .iter()
-            .filter_map(|symbol| {
+            .map(|symbol| {
                 let neighbours = schematic
                     .parts
                     .iter()
-                    .filter_map(|part| {
-                        if part.is_neighbour(&symbol.node) {
-                            Some(part.value)
+                    .filter(|part| part.is_neighbour(&symbol.node))
+                    .map(|part| part.value)
+                    .collect::<Vec<_>>();
+                match neighbours[..] {
+                    [] => SymbolKind::Lone,
+                    [a, b] if symbol.kind == '*' => SymbolKind::Gear(a, b),
+                    _ => SymbolKind::Other,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut output = String::new();
+        if format == Format::Html {
+            output.push_str(HTML_HEADER);
+        }
+        for (i, row) in rows.iter().enumerate() {
+            let mut gears = Vec::new();
+            let mut j = 0;
+            while j < row.len() {
+                match cells[i][j] {
+                    Cell::Empty => {
+                        push_text(&mut output, format, &row[j].to_string());
+                        j += 1;
+                    }
+                    Cell::Part(index) => {
+                        let len = schematic.parts[index].nodes.len();
+                        let text = row[j..j + len].iter().collect::<String>();
+                        let (class, title) = if counted[index] {
+                            ("counted", "counted")
                         } else {
-                            None
+                            ("ignored", "ignored: no adjacent symbol")
+                        };
+                        push_span(&mut output, format, class, title, &text);
+                        j += len;
+                    }
+                    Cell::Symbol(index) => {
+                        let text = row[j].to_string();
+                        match kinds[index] {
+                            SymbolKind::Gear(a, b) => {
+                                let ratio = a
+                                    .checked_mul(b)
+                                    .map_or_else(|| "overflow".to_string(), |r| r.to_string());
+                                let title = format!("gear: {a} * {b} = {ratio}");
+                                push_span(&mut output, format, "gear", &title, &text);
+                                gears.push(format!("({i},{j}) {a} * {b} = {ratio}"));
+                            }
+                            SymbolKind::Lone => {
+                                push_span(&mut output, format, "lone", "lone symbol", &text);
+                            }
+                            SymbolKind::Other => {
+                                push_span(&mut output, format, "symbol", "symbol", &text);
+                            }
                         }
-                    })
-                    .collect::<Vec<_>>();
-                if neighbours.len() == 2 {
-                    Some(neighbours.into_iter().product::<u64>())
-                } else {
-                    None
+                        j += 1;
+                    }
                 }
+            }
+            if !gears.is_empty() {
+                output.push_str("   ");
+                push_span(&mut output, format, "note", "", &gears.join(", "));
+            }
+            output.push('\n');
+        }
+        if format == Format::Html {
+            output.push_str(HTML_FOOTER);
+        }
+        Ok(output)
+    }
+
+    fn push_text(output: &mut String, format: Format, text: &str) {
+        match format {
+            Format::Ansi => output.push_str(text),
+            Format::Html => {
+                for c in text.chars() {
+                    match c {
+                        '&' => output.push_str("&amp;"),
+                        '<' => output.push_str("&lt;"),
+                        '>' => output.push_str("&gt;"),
+                        '"' => output.push_str("&quot;"),
+                        c => output.push(c),
+                    }
+                }
+            }
+        }
+    }
+
+    fn push_span(output: &mut String, format: Format, class: &str, title: &str, text: &str) {
+        match format {
+            Format::Ansi => {
+                let colour = match class {
+                    "counted" => "32",
+                    "ignored" => "2;31",
+                    "gear" => "1;33",
+                    "lone" => "1;7;35",
+                    "symbol" => "36",
+                    _ => "2",
+                };
+                write!(output, "\x1b[{colour}m{text}\x1b[0m").expect("writing to string");
+            }
+            Format::Html => {
+                write!(output, "<span class=\"{class}\" title=\"").expect("writing to string");
+                push_text(output, format, title);
+                output.push_str("\">");
+                push_text(output, format, text);
+                output.push_str("</span>");
+            }
+        }
+    }
+
+    const HTML_HEADER: &str = "\
+<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>Schematic</title>
+<style>
+body { background: #1e1e1e; color: #808080; }
+.counted { color: #4ec94e; }
+.ignored { color: #e05050; text-decoration: line-through; }
+.gear { color: #1e1e1e; background: #e0c040; font-weight: bold; }
+.lone { color: #1e1e1e; background: #d060d0; }
+.symbol { color: #50c0e0; }
+.note { color: #e0c040; }
+</style>
+</head>
+<body>
+<p>
+<span class=\"counted\">counted part</span>
+<span class=\"ignored\">ignored part</span>
+<span class=\"gear\">gear</span>
+<span class=\"symbol\">symbol</span>
+<span class=\"lone\">lone symbol</span>
+</p>
+<pre>
+";
+
+    const HTML_FOOTER: &str = "</pre>\n</body>\n</html>\n";
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+.........%";
+
+        #[test]
+        fn render_ansi() {
+            let output = render(EXAMPLE_1, ParseOptions::default(), Format::Ansi).unwrap();
+            let lines = output.lines().collect::<Vec<_>>();
+            assert_eq!(lines.len(), 11);
+            assert_eq!(lines[0], "\x1b[32m467\x1b[0m..\x1b[2;31m114\x1b[0m..");
+            assert!(lines[1].ends_with("\x1b[2m(1,3) 467 * 35 = 16345\x1b[0m"));
+            assert_eq!(lines[10], ".........\x1b[1;7;35m%\x1b[0m");
+        }
+
+        #[test]
+        fn render_html() {
+            let output = render(EXAMPLE_1, ParseOptions::default(), Format::Html).unwrap();
+            assert!(output.starts_with("<!DOCTYPE html>"));
+            assert_eq!(output.matches("class=\"counted\"").count(), 9);
+            assert_eq!(output.matches("class=\"ignored\"").count(), 3);
+            assert_eq!(output.matches("class=\"gear\"").count(), 3);
+            assert_eq!(output.matches("class=\"lone\"").count(), 2);
+            assert!(output.contains("title=\"gear: 755 * 598 = 451490\""));
+        }
+
+        #[test]
+        fn render_overflowing_gear() {
+            let input = "5000000000*5000000000";
+            let output = render(input, ParseOptions::default(), Format::Ansi).unwrap();
+            assert!(output.contains("5000000000 * 5000000000 = overflow"));
+        }
+    }
+}
+
+pub mod clusters {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Bounds {
+        pub top: i64,
+        pub left: i64,
+        pub bottom: i64,
+        pub right: i64,
+    }
+
+    /// A group of parts and symbols linked through chains of 8-adjacent items.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Cluster {
+        pub parts: Vec<i64>,
+        pub symbols: Vec<char>,
+        pub bounds: Bounds,
+    }
+
+    impl Cluster {
+        pub fn size(&self) -> usize {
+            self.parts.len() + self.symbols.len()
+        }
+
+        pub fn sum(&self) -> i64 {
+            self.parts.iter().sum()
+        }
+
+        pub fn contains_symbol(&self, symbol: char) -> bool {
+            self.symbols.contains(&symbol)
+        }
+    }
+
+    /// Clusters ordered by their top-left corner, then by their first part or
+    /// symbol, lone parts and symbols included.
+    pub fn find(
+        input: &str,
+        options: ParseOptions,
+    ) -> Result<Vec<Cluster>, Box<dyn std::error::Error>> {
+        let schematic = SchematicGraph::parse(input, options, |_| true)?;
+        let items = schematic
+            .parts
+            .iter()
+            .map(|part| &part.nodes[..])
+            .chain(
+                schematic
+                    .symbols
+                    .iter()
+                    .map(|symbol| std::slice::from_ref(&symbol.node)),
+            )
+            .collect::<Vec<_>>();
+        let cells = items
+            .iter()
+            .enumerate()
+            .flat_map(|(item, nodes)| nodes.iter().map(move |node| ((node.0, node.1), item)))
+            .collect::<HashMap<_, _>>();
+
+        let mut roots = (0..items.len()).collect::<Vec<_>>();
+        fn root(roots: &mut [usize], mut item: usize) -> usize {
+            while roots[item] != item {
+                roots[item] = roots[roots[item]];
+                item = roots[item];
+            }
+            item
+        }
+        for (item, nodes) in items.iter().enumerate() {
+            for neighbour in nodes.iter().flat_map(Node::neighbours) {
+                if let Some(&other) = cells.get(&(neighbour.0, neighbour.1)) {
+                    let (a, b) = (root(&mut roots, item), root(&mut roots, other));
+                    roots[a.max(b)] = a.min(b);
+                }
+            }
+        }
+
+        // a root is its cluster's smallest item, so clusters come out in item order
+        let mut clusters = Vec::<Cluster>::new();
+        let mut slots = vec![None; items.len()];
+        for (item, nodes) in items.iter().enumerate() {
+            let slot = *slots[root(&mut roots, item)].get_or_insert_with(|| {
+                clusters.push(Cluster {
+                    parts: Vec::new(),
+                    symbols: Vec::new(),
+                    bounds: Bounds {
+                        top: nodes[0].0,
+                        left: nodes[0].1,
+                        bottom: nodes[0].0,
+                        right: nodes[0].1,
+                    },
+                });
+                clusters.len() - 1
+            });
+            let cluster = &mut clusters[slot];
+            match schematic.parts.get(item) {
+                Some(part) => cluster.parts.push(part.value),
+                None => cluster
+                    .symbols
+                    .push(schematic.symbols[item - schematic.parts.len()].kind),
+            }
+            for node in nodes.iter() {
+                cluster.bounds.top = cluster.bounds.top.min(node.0);
+                cluster.bounds.left = cluster.bounds.left.min(node.1);
+                cluster.bounds.bottom = cluster.bounds.bottom.max(node.0);
+                cluster.bounds.right = cluster.bounds.right.max(node.1);
+            }
+        }
+        // stable, so clusters sharing a corner keep their item order
+        clusters.sort_by_key(|cluster| (cluster.bounds.top, cluster.bounds.left));
+        Ok(clusters)
+    }
+
+    /// Sum of the parts in every cluster that contains at least one `symbol`.
+    pub fn solve(input: &str, symbol: char) -> Result<i64, Box<dyn std::error::Error>> {
+        Ok(find(input, ParseOptions::default())?
+            .iter()
+            .filter(|cluster| cluster.contains_symbol(symbol))
+            .map(Cluster::sum)
+            .sum())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/3-1.txt");
+
+        #[test]
+        fn find_example_1() {
+            let clusters = find(EXAMPLE_1, ParseOptions::default()).unwrap();
+            assert_eq!(
+                clusters
+                    .iter()
+                    .map(|cluster| (cluster.size(), cluster.sum()))
+                    .collect::<Vec<_>>(),
+                vec![
+                    (3, 502),
+                    (1, 114),
+                    (2, 633),
+                    (2, 617),
+                    (2, 592),
+                    (1, 58),
+                    (3, 1353),
+                    (2, 664)
+                ]
+            );
+            assert_eq!(
+                clusters[6].bounds,
+                Bounds {
+                    top: 7,
+                    left: 5,
+                    bottom: 9,
+                    right: 8
+                }
+            );
+        }
+
+        #[test]
+        fn find_shared_corner() {
+            // the lone `#` and the chain 1-*-2-3 both start at row 0, column 0
+            let input = "#..1\n..*.\n.2..\n3...";
+            for _ in 0..20 {
+                let clusters = find(input, ParseOptions::default()).unwrap();
+                assert_eq!(
+                    clusters.iter().map(Cluster::sum).collect::<Vec<_>>(),
+                    vec![6, 0]
+                );
+            }
+        }
+
+        #[test]
+        fn solve_example_1() {
+            assert_eq!(solve(EXAMPLE_1, '#').unwrap(), 633);
+            assert_eq!(solve(EXAMPLE_1, '*').unwrap(), 502 + 617 + 1353);
+        }
+    }
+}
+
+pub mod repl {
+    use super::*;
+    use crate::repl::{arg, unknown};
+
+    #[derive(Debug)]
+    pub struct Schematic(SchematicGraph);
+
+    impl Schematic {
+        pub fn parse(input: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            Ok(Self(SchematicGraph::parse(
+                input,
+                ParseOptions::default(),
+                |_| true,
+            )?))
+        }
+    }
+
+    impl crate::repl::Model for Schematic {
+        fn help(&self) -> &'static str {
+            "neighbours <row> <column>"
+        }
+
+        fn query(&self, words: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+            let ["neighbours", row, column] = words else {
+                return Err(unknown(words, self.help()));
+            };
+            let node = Node(arg(row, "row")?, arg(column, "column")?);
+            let parts = self
+                .0
+                .parts
+                .iter()
+                .filter(|part| part.is_neighbour(&node))
+                .map(|part| part.value.to_string())
+                .collect::<Vec<_>>();
+            let symbols = self
+                .0
+                .symbols
+                .iter()
+                .filter(|symbol| symbol.node != node && symbol.node.is_neighbour(&node))
+                .map(|symbol| symbol.kind.to_string())
+                .collect::<Vec<_>>();
+            let list = |items: Vec<String>| match items.is_empty() {
+                true => "none".to_string(),
+                false => items.join(", "),
+            };
+            Ok(format!(
+                "parts: {}\nsymbols: {}",
+                list(parts),
+                list(symbols)
+            ))
+        }
+    }
+}
+
+pub mod part2 {
+    use super::*;
+    use crate::num::Number;
+    use crate::observe::{Event, Observer};
+    use std::collections::HashMap;
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        solve_with(input, ParseOptions::default())
+    }
+
+    pub fn solve_with<N: Number>(
+        input: &str,
+        options: ParseOptions,
+    ) -> Result<N, Box<dyn std::error::Error>> {
+        solve_observed(input, options, &())
+    }
+
+    pub fn solve_observed<N, O>(
+        input: &str,
+        options: ParseOptions,
+        observer: &O,
+    ) -> Result<N, Box<dyn std::error::Error>>
+    where
+        N: Number,
+        O: Observer + ?Sized,
+    {
+        let schematic = SchematicGraph::parse(input, options, |c| c == '*')?;
+        let part_cells = schematic
+            .parts
+            .iter()
+            .enumerate()
+            .flat_map(|(index, part)| part.nodes.iter().map(move |node| (*node, index)))
+            .collect::<HashMap<_, _>>();
+        schematic
+            .symbols
+            .iter()
+            .try_fold(N::zero(), |total, symbol| {
+                let mut neighbours = symbol
+                    .node
+                    .neighbours()
+                    .filter_map(|node| part_cells.get(&node).copied())
+                    .collect::<Vec<_>>();
+                neighbours.sort();
+                neighbours.dedup();
+                let [a, b] = neighbours[..] else {
+                    return Ok(total);
+                };
+                let Node(i, j) = symbol.node;
+                observer.event(Event::GearAccepted {
+                    row: i,
+                    column: j,
+                    parts: [schematic.parts[a].value, schematic.parts[b].value],
+                });
+                let value = |index: usize| {
+                    let value = schematic.parts[index].value;
+                    N::from_i64(value).ok_or_else(|| format!("part {value} at row {i} is negative"))
+                };
+                value(a)?
+                    .checked_mul(&value(b)?)
+                    .and_then(|ratio| total.checked_add(&ratio))
+                    .ok_or_else(|| {
+                        format!("gear at row {i}, column {j} overflows the total").into()
+                    })
             })
-            .sum()
     }
 
     #[cfg(test)]
     mod tests {
         use super::*;
 
-        const EXAMPLE_1: &str = "
-            467..114..
-            ...*......
-            ..35..633.
-            ......#...
-            617*......
-            .....+.58.
-            ..592.....
-            ......755.
-            ...$.*....
-            .664.598..";
+        const EXAMPLE_1: &str = include_str!("../data/examples/3-1.txt");
 
         #[test]
         fn solve_example1() {
-            assert_eq!(part2::solve(EXAMPLE_1), 467835);
+            assert_eq!(part2::solve(EXAMPLE_1).unwrap(), 467835);
+        }
+
+        #[test]
+        fn overflow() {
+            let input = "5000000000*5000000000";
+            assert_eq!(
+                solve(input).unwrap_err().to_string(),
+                "gear at row 0, column 10 overflows the total"
+            );
+            let options = ParseOptions {
+                signed: true,
+                ..Default::default()
+            };
+            assert_eq!(
+                solve_with::<u64>("-12*3.\n......", options)
+                    .unwrap_err()
+                    .to_string(),
+                "part -12 at row 0 is negative"
+            );
+        }
+
+        #[test]
+        fn solve_signed() {
+            let options = ParseOptions {
+                signed: true,
+                ..Default::default()
+            };
+            assert_eq!(solve_with::<i64>("-12*3.\n......", options).unwrap(), -36);
         }
     }
 }
 
 pub mod part1 {
     use super::*;
+    use crate::num::Number;
+    use std::collections::HashSet;
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        solve_with(input, ParseOptions::default())
+    }
 
-    pub fn solve(input: &str) -> u64 {
-        let schematic = SchematicGraph::parse(input, |_| true);
+    pub fn solve_with<N: Number>(
+        input: &str,
+        options: ParseOptions,
+    ) -> Result<N, Box<dyn std::error::Error>> {
+        let schematic = SchematicGraph::parse(input, options, |_| true)?;
+        let symbol_cells = schematic
+            .symbols
+            .iter()
+            .map(|symbol| symbol.node)
+            .collect::<HashSet<_>>();
         schematic
             .parts
             .iter()
-            .filter_map(|part| {
-                schematic
-                    .symbols
+            .filter(|part| {
+                part.nodes
                     .iter()
-                    .find(|symbol| part.is_neighbour(&symbol.node))
-                    .map(|_| part.value)
+                    .flat_map(Node::neighbours)
+                    .any(|node| symbol_cells.contains(&node))
+            })
+            .try_fold(N::zero(), |total, part| {
+                let row = part.nodes[0].0;
+                N::from_i64(part.value)
+                    .ok_or_else(|| format!("part {} at row {row} is negative", part.value))?
+                    .checked_add(&total)
+                    .ok_or_else(|| format!("part at row {row} overflows the total").into())
             })
-            .sum()
     }
 
     #[cfg(test)]
     mod tests {
         use super::*;
 
-        const EXAMPLE_1: &str = "
-            467..114..
-            ...*......
-            ..35..633.
-            ......#...
-            617*......
-            .....+.58.
-            ..592.....
-            ......755.
-            ...$.*....
-            .664.598..";
+        const EXAMPLE_1: &str = include_str!("../data/examples/3-1.txt");
 
         #[test]
         fn solve_example_1() {
-            assert_eq!(solve(EXAMPLE_1), 4361);
+            assert_eq!(solve(EXAMPLE_1).unwrap(), 4361);
+        }
+
+        #[test]
+        fn parse_non_ascii() {
+            let schematic =
+                SchematicGraph::parse("é.12\n..€.", ParseOptions::default(), |_| true).unwrap();
+            assert_eq!(schematic.parts.len(), 1);
+            assert_eq!(schematic.parts[0].value, 12);
+            assert_eq!(schematic.parts[0].nodes[0].1, 2);
+            assert_eq!(schematic.symbols[0].node.1, 0);
+            assert_eq!(schematic.symbols[1].node.1, 2);
+            assert_eq!(solve("é.12\n..€.").unwrap(), 12);
+        }
+
+        #[test]
+        fn parse_ragged() {
+            let input = "467..\n...*......\n..35";
+            assert_eq!(
+                SchematicGraph::parse(input, ParseOptions::default(), |_| true)
+                    .unwrap_err()
+                    .to_string(),
+                "row 1 has 10 columns, expected 5"
+            );
+            let options = ParseOptions {
+                lenient: true,
+                ..Default::default()
+            };
+            assert_eq!(solve_with::<u64>(input, options).unwrap(), 502);
+        }
+
+        #[test]
+        fn parse_signed() {
+            let input = "..-5.-\n-.10-3";
+            let schematic =
+                SchematicGraph::parse(input, ParseOptions::default(), |_| true).unwrap();
+            assert_eq!(
+                schematic
+                    .parts
+                    .iter()
+                    .map(|part| part.value)
+                    .collect::<Vec<_>>(),
+                vec![5, 10, 3]
+            );
+            let options = ParseOptions {
+                signed: true,
+                ..Default::default()
+            };
+            let schematic = SchematicGraph::parse(input, options, |_| true).unwrap();
+            assert_eq!(
+                schematic
+                    .parts
+                    .iter()
+                    .map(|part| part.value)
+                    .collect::<Vec<_>>(),
+                vec![-5, 10, -3]
+            );
+            assert_eq!(schematic.parts[0].nodes.len(), 2);
+            assert_eq!(schematic.symbols.len(), 2);
         }
     }
 }
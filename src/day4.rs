@@ -1,12 +1,45 @@
+use crate::num::Number;
+use crate::observe::{Event, Observer};
+use std::collections::HashSet;
+
+/// Set of card numbers backed by a bitset for the small values real cards use,
+/// falling back to hashing for anything larger.
+#[derive(Debug, Default)]
+struct NumberSet {
+    small: u128,
+    large: HashSet<u64>,
+}
+
+impl NumberSet {
+    /// Returns whether `n` was newly inserted.
+    fn insert(&mut self, n: u64) -> bool {
+        if n < u128::BITS as u64 {
+            let present = self.small & (1 << n) != 0;
+            self.small |= 1 << n;
+            !present
+        } else {
+            self.large.insert(n)
+        }
+    }
+
+    fn contains(&self, n: u64) -> bool {
+        if n < u128::BITS as u64 {
+            self.small & (1 << n) != 0
+        } else {
+            self.large.contains(&n)
+        }
+    }
+}
+
 #[derive(Debug)]
-struct Card {
+pub struct Card {
     id: u64,
-    winning_numbers: Vec<u64>,
+    winning_numbers: NumberSet,
     player_numbers: Vec<u64>,
 }
 
 impl<'a> Card {
-    fn parse(input: &str) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn parse(input: &str) -> Result<Self, Box<dyn std::error::Error>> {
         let (id, rest) = input.split_once(":").ok_or("No numbers")?;
         let id = id
             .split_whitespace()
@@ -16,8 +49,8 @@ impl<'a> Card {
 
         let (winning_numbers, player_numbers) =
             rest.split_once("|").ok_or("No separator for numbers")?;
-        let winning_numbers = Self::parse_numbers(winning_numbers, 5)?;
-        let player_numbers = Self::parse_numbers(player_numbers, 8)?;
+        let (_, winning_numbers) = Self::parse_numbers(winning_numbers, "winning", id)?;
+        let (player_numbers, _) = Self::parse_numbers(player_numbers, "player", id)?;
         Ok(Self {
             id,
             winning_numbers,
@@ -25,61 +58,397 @@ impl<'a> Card {
         })
     }
 
-    fn parse_numbers(input: &str, length: usize) -> Result<Vec<u64>, Box<dyn std::error::Error>> {
-        let output = input.trim().split_whitespace().fold(
-            Ok(Vec::<u64>::with_capacity(length)),
-            |acc: Result<Vec<u64>, Box<dyn std::error::Error>>, n| {
-                let mut acc = acc?;
-                acc.push(n.parse()?);
-                Ok(acc)
+    fn parse_numbers(
+        input: &str,
+        kind: &str,
+        id: u64,
+    ) -> Result<(Vec<u64>, NumberSet), Box<dyn std::error::Error>> {
+        input.split_whitespace().try_fold(
+            (Vec::new(), NumberSet::default()),
+            |(mut numbers, mut set), n| -> Result<_, Box<dyn std::error::Error>> {
+                let n = n.parse()?;
+                if !set.insert(n) {
+                    return Err(format!("Duplicate {kind} number {n} on card {id}").into());
+                }
+                numbers.push(n);
+                Ok((numbers, set))
             },
-        )?;
-        Ok(output)
+        )
     }
 
-    fn get_matching_numbers(&'a self) -> impl Iterator<Item = &u64> + 'a {
+    fn get_matching_numbers(&'a self) -> impl Iterator<Item = &'a u64> + 'a {
         self.player_numbers
             .iter()
-            .filter(|n| self.winning_numbers.contains(n))
+            .filter(|n| self.winning_numbers.contains(**n))
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn matches(&self) -> usize {
+        self.get_matching_numbers().count()
     }
 }
 
-pub mod part2 {
+fn parse_cards(input: &str) -> Result<Vec<Card>, Box<dyn std::error::Error>> {
+    Ok(crate::par::map_lines(input, |_, line| {
+        Card::parse(line).map_err(|e| e.to_string())
+    })?)
+}
+
+pub mod cascade {
     use super::*;
-    use std::collections::HashMap;
 
-    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
-        let mut card_inventory = HashMap::<u64, u64>::new();
-        for line in input.lines() {
-            let card = Card::parse(line)?;
-            let current_card_count = *card_inventory
-                .entry(card.id)
-                .and_modify(|count| *count += 1)
-                .or_insert(1);
-            let total_matches = card.get_matching_numbers().count();
-            if total_matches > 0 {
-                for winner in card.id + 1..=card.id + total_matches as u64 {
-                    card_inventory
-                        .entry(winner)
-                        .and_modify(|count| *count += current_card_count)
-                        .or_insert(current_card_count);
+    /// How the cascade treats card ids that are missing or out of order.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub enum IdPolicy {
+        /// Cards must be listed as 1, 2, 3, ... with nothing skipped.
+        Strict,
+        /// Cards are sorted by id and copies won for ids with no card are dropped.
+        #[default]
+        Lenient,
+    }
+
+    /// Copies held of each card once every prize has been paid out.
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct Cascade<N = u64> {
+        pub ids: Vec<u64>,
+        pub copies: Vec<N>,
+    }
+
+    impl<N: Number> Cascade<N> {
+        pub fn parse(input: &str, policy: IdPolicy) -> Result<Self, Box<dyn std::error::Error>> {
+            Self::run(&parse_cards(input)?, policy)
+        }
+
+        pub fn run(cards: &[Card], policy: IdPolicy) -> Result<Self, Box<dyn std::error::Error>> {
+            Self::run_observed(cards, policy, &())
+        }
+
+        pub fn run_observed<O: Observer + ?Sized>(
+            cards: &[Card],
+            policy: IdPolicy,
+            observer: &O,
+        ) -> Result<Self, Box<dyn std::error::Error>> {
+            let mut cards = cards
+                .iter()
+                .map(|card| (card.id, card.matches() as u64))
+                .collect::<Vec<_>>();
+            match policy {
+                IdPolicy::Strict => {
+                    if let Some((expected, (id, _))) = (1..)
+                        .zip(cards.iter())
+                        .find(|(expected, (id, _))| id != expected)
+                    {
+                        return Err(
+                            format!("Found card {id} where card {expected} was expected").into(),
+                        );
+                    }
+                }
+                IdPolicy::Lenient => {
+                    cards.sort_by_key(|(id, _)| *id);
+                    if let Some(pair) = cards.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+                        return Err(format!("Card {} appears more than once", pair[0].0).into());
+                    }
+                }
+            }
+
+            let mut copies = vec![N::one(); cards.len()];
+            for (i, (id, matches)) in cards.iter().enumerate() {
+                let last_prize = id.saturating_add(*matches);
+                for j in i + 1..cards.len() {
+                    if cards[j].0 > last_prize {
+                        break;
+                    }
+                    observer.event(Event::CascadeStep {
+                        card: *id,
+                        prize: cards[j].0,
+                    });
+                    copies[j] = copies[j]
+                        .checked_add(&copies[i])
+                        .ok_or_else(|| format!("Copies of card {} overflow", cards[j].0))?;
                 }
             }
+            Ok(Self {
+                ids: cards.into_iter().map(|(id, _)| id).collect(),
+                copies,
+            })
+        }
+
+        pub fn total(&self) -> Result<N, Box<dyn std::error::Error>> {
+            self.ids
+                .iter()
+                .zip(&self.copies)
+                .try_fold(N::zero(), |total, (id, copies)| {
+                    total
+                        .checked_add(copies)
+                        .ok_or_else(|| format!("Total overflows at card {id}").into())
+                })
         }
-        Ok(card_inventory.values().sum())
     }
 
     #[cfg(test)]
     mod tests {
         use super::*;
 
-        const EXAMPLE_1: &str = "\
-            Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-            Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-            Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-            Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-            Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-            Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+        const EXAMPLE_1: &str = include_str!("../data/examples/4-1.txt");
+
+        #[test]
+        fn copies_example_1() {
+            let cascade: Cascade = Cascade::parse(EXAMPLE_1, IdPolicy::Strict).unwrap();
+            assert_eq!(cascade.ids, vec![1, 2, 3, 4, 5, 6]);
+            assert_eq!(cascade.copies, vec![1, 2, 4, 8, 14, 1]);
+        }
+
+        #[test]
+        fn clamps_to_last_card() {
+            let input = "Card 1: 1 2 | 1 3\nCard 2: 1 2 3 | 1 2 3";
+            let cascade: Cascade = Cascade::parse(input, IdPolicy::Strict).unwrap();
+            assert_eq!(cascade.copies, vec![1, 2]);
+            assert_eq!(cascade.total().unwrap(), 3);
+        }
+
+        #[test]
+        fn out_of_order_and_missing() {
+            let input = "Card 4: 1 | 2\nCard 1: 1 2 3 | 1 2 3\nCard 2: 5 | 5";
+            assert_eq!(
+                Cascade::<u64>::parse(input, IdPolicy::Strict)
+                    .unwrap_err()
+                    .to_string(),
+                "Found card 4 where card 1 was expected"
+            );
+            let cascade: Cascade = Cascade::parse(input, IdPolicy::Lenient).unwrap();
+            assert_eq!(cascade.ids, vec![1, 2, 4]);
+            assert_eq!(cascade.copies, vec![1, 2, 2]);
+            assert_eq!(
+                Cascade::<u64>::parse("Card 1: 1 | 1\nCard 1: 2 | 3", IdPolicy::Lenient)
+                    .unwrap_err()
+                    .to_string(),
+                "Card 1 appears more than once"
+            );
+        }
+
+        #[test]
+        fn copies_overflow() {
+            // two matches per card make the copies grow like the Fibonacci numbers
+            let input = (1..=100)
+                .map(|id| format!("Card {id}: 1 2 | 1 2"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            assert_eq!(
+                Cascade::<u64>::parse(&input, IdPolicy::Strict)
+                    .unwrap_err()
+                    .to_string(),
+                "Copies of card 92 overflow"
+            );
+        }
+    }
+}
+
+pub mod scoring {
+    use super::*;
+    use cascade::{Cascade, IdPolicy};
+
+    /// Turns a deck of scratchcards into a single score.
+    pub trait ScoringRule {
+        fn score(&self, cards: &[Card]) -> Result<u64, Box<dyn std::error::Error>>;
+    }
+
+    pub(super) fn sum_per_card<N, T>(
+        cards: &[Card],
+        points: T,
+    ) -> Result<N, Box<dyn std::error::Error>>
+    where
+        N: Number,
+        T: Fn(usize) -> Option<N> + Send + Sync,
+    {
+        crate::par::map_items(cards, |card| {
+            points(card.matches()).ok_or_else(|| format!("Points for card {} overflow", card.id))
+        })?
+        .into_iter()
+        .zip(cards)
+        .try_fold(N::zero(), |acc, (card_points, card)| {
+            acc.checked_add(&card_points)
+                .ok_or_else(|| format!("Total overflows at card {}", card.id).into())
+        })
+    }
+
+    /// One point for the first match, doubled for every match after it.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct Doubling;
+
+    impl Doubling {
+        pub(super) fn points<N: Number>(matches: usize) -> Option<N> {
+            let two = N::from_u64(2)?;
+            match matches {
+                0 => Some(N::zero()),
+                n => (1..n).try_fold(N::one(), |points, _| points.checked_mul(&two)),
+            }
+        }
+    }
+
+    impl ScoringRule for Doubling {
+        fn score(&self, cards: &[Card]) -> Result<u64, Box<dyn std::error::Error>> {
+            sum_per_card(cards, Doubling::points)
+        }
+    }
+
+    /// One point per match.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct Linear;
+
+    impl ScoringRule for Linear {
+        fn score(&self, cards: &[Card]) -> Result<u64, Box<dyn std::error::Error>> {
+            sum_per_card(cards, |matches| Some(matches as u64))
+        }
+    }
+
+    /// Points follow the Fibonacci sequence 1, 2, 3, 5, 8, ... by number of matches.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct Fibonacci;
+
+    impl ScoringRule for Fibonacci {
+        fn score(&self, cards: &[Card]) -> Result<u64, Box<dyn std::error::Error>> {
+            sum_per_card(cards, |matches| {
+                (0..matches)
+                    .try_fold((0u64, 1u64), |(a, b), _| Some((b, a.checked_add(b)?)))
+                    .map(|(_, b)| if matches == 0 { 0 } else { b })
+            })
+        }
+    }
+
+    /// Each match wins a copy of one of the following cards; the score is the
+    /// number of cards held at the end.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct CopyCascade(pub IdPolicy);
+
+    impl ScoringRule for CopyCascade {
+        fn score(&self, cards: &[Card]) -> Result<u64, Box<dyn std::error::Error>> {
+            Cascade::run(cards, self.0)?.total()
+        }
+    }
+
+    /// Points per card computed by a closure from its number of matches.
+    pub struct Custom<T>(pub T);
+
+    impl<T> ScoringRule for Custom<T>
+    where
+        T: Fn(usize) -> u64 + Send + Sync,
+    {
+        fn score(&self, cards: &[Card]) -> Result<u64, Box<dyn std::error::Error>> {
+            sum_per_card(cards, |matches| Some((self.0)(matches)))
+        }
+    }
+
+    pub fn solve(input: &str, rule: &dyn ScoringRule) -> Result<u64, Box<dyn std::error::Error>> {
+        rule.score(&parse_cards(input)?)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/4-1.txt");
+
+        #[test]
+        fn solve_example_1() {
+            // matches per card: 4, 2, 2, 1, 0, 0
+            assert_eq!(solve(EXAMPLE_1, &Doubling).unwrap(), 13);
+            assert_eq!(solve(EXAMPLE_1, &Linear).unwrap(), 9);
+            assert_eq!(solve(EXAMPLE_1, &Fibonacci).unwrap(), 10);
+            assert_eq!(solve(EXAMPLE_1, &CopyCascade::default()).unwrap(), 30);
+            assert_eq!(solve(EXAMPLE_1, &Custom(|n| (n * n) as u64)).unwrap(), 25);
+        }
+
+        #[test]
+        fn doubling_overflow() {
+            let numbers = (1..=65)
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let input = format!("Card 1: {numbers} | {numbers}");
+            assert_eq!(
+                solve(&input, &Doubling).unwrap_err().to_string(),
+                "Points for card 1 overflow"
+            );
+        }
+    }
+}
+
+pub mod repl {
+    use super::*;
+    use crate::repl::{arg, unknown};
+    use cascade::Cascade;
+
+    #[derive(Debug)]
+    pub struct Cards(Vec<Card>);
+
+    impl Cards {
+        pub fn parse(input: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            Ok(Self(parse_cards(input)?))
+        }
+    }
+
+    impl crate::repl::Model for Cards {
+        fn help(&self) -> &'static str {
+            "card <id> matches|points|copies"
+        }
+
+        fn query(&self, words: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+            let ["card", id, detail] = words else {
+                return Err(unknown(words, self.help()));
+            };
+            let id = arg::<u64>(id, "card id")?;
+            let card = self
+                .0
+                .iter()
+                .find(|card| card.id == id)
+                .ok_or_else(|| format!("no card {id}"))?;
+            match *detail {
+                "matches" => Ok(card.matches().to_string()),
+                "points" => Ok(scoring::Doubling::points::<u64>(card.matches())
+                    .ok_or("points overflow")?
+                    .to_string()),
+                "copies" => {
+                    let cascade: Cascade = Cascade::run(&self.0, Default::default())?;
+                    let index = cascade
+                        .ids
+                        .iter()
+                        .position(|card| *card == id)
+                        .expect("every card is in the cascade");
+                    Ok(cascade.copies[index].to_string())
+                }
+                _ => Err(unknown(words, self.help())),
+            }
+        }
+    }
+}
+
+pub mod part2 {
+    use super::*;
+    use scoring::CopyCascade;
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        scoring::solve(input, &CopyCascade::default())
+    }
+
+    pub fn solve_as<N: Number>(input: &str) -> Result<N, Box<dyn std::error::Error>> {
+        cascade::Cascade::parse(input, Default::default())?.total()
+    }
+
+    pub fn solve_observed<O: Observer + ?Sized>(
+        input: &str,
+        observer: &O,
+    ) -> Result<u64, Box<dyn std::error::Error>> {
+        cascade::Cascade::run_observed(&parse_cards(input)?, Default::default(), observer)?.total()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/4-1.txt");
 
         #[test]
         fn solve_example_1() {
@@ -92,38 +461,40 @@ pub mod part1 {
     use super::*;
 
     pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
-        Ok(input
-            .lines()
-            .map(|card| {
-                let card = Card::parse(card)?;
-                Ok(card
-                    .get_matching_numbers()
-                    .fold(0, |acc, _| if acc == 0 { 1 } else { 2 * acc }))
-            })
-            .fold(
-                Ok(0),
-                |acc: Result<u64, Box<dyn std::error::Error>>,
-                 curr: Result<u64, Box<dyn std::error::Error>>| {
-                    Ok(acc? + curr?)
-                },
-            )?)
+        scoring::solve(input, &scoring::Doubling)
+    }
+
+    pub fn solve_as<N: Number>(input: &str) -> Result<N, Box<dyn std::error::Error>> {
+        scoring::sum_per_card(&parse_cards(input)?, scoring::Doubling::points)
     }
 
     #[cfg(test)]
     mod tests {
         use super::*;
 
-        const EXAMPLE_1: &str = "\
-            Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-            Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-            Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-            Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-            Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-            Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+        const EXAMPLE_1: &str = include_str!("../data/examples/4-1.txt");
 
         #[test]
         fn solve_example_1() {
             assert_eq!(part1::solve(EXAMPLE_1).unwrap(), 13);
         }
+
+        #[test]
+        fn parse() {
+            let card = Card::parse("Card 7: 1 150 3 | 3 150 2 1 9 127").unwrap();
+            assert_eq!(card.id, 7);
+            assert_eq!(
+                card.get_matching_numbers().collect::<Vec<_>>(),
+                vec![&3, &150, &1]
+            );
+            assert_eq!(
+                Card::parse("Card 2: 4 5 4 | 1 2").unwrap_err().to_string(),
+                "Duplicate winning number 4 on card 2"
+            );
+            assert_eq!(
+                Card::parse("Card 3: 4 5 | 1 2 1").unwrap_err().to_string(),
+                "Duplicate player number 1 on card 3"
+            );
+        }
     }
 }
@@ -21,36 +21,40 @@ mod almanac {
                 }
                 let (map_kind, map_lines) = line.split_once("\n").ok_or("map missing data")?;
                 match map_kind {
-                    kind if kind.starts_with("seed-to-soil map") => {
-                        output.insert_map(Property::Seed, Property::Soil, Map::parse(map_lines)?)
-                    }
+                    kind if kind.starts_with("seed-to-soil map") => output.insert_map(
+                        Property::Seed,
+                        Property::Soil,
+                        Map::parse(kind, map_lines)?,
+                    ),
                     kind if kind.starts_with("soil-to-fertilizer map") => output.insert_map(
                         Property::Soil,
                         Property::Fertilizer,
-                        Map::parse(map_lines)?,
+                        Map::parse(kind, map_lines)?,
                     ),
                     kind if kind.starts_with("fertilizer-to-water map") => output.insert_map(
                         Property::Fertilizer,
                         Property::Water,
-                        Map::parse(map_lines)?,
+                        Map::parse(kind, map_lines)?,
+                    ),
+                    kind if kind.starts_with("water-to-light map") => output.insert_map(
+                        Property::Water,
+                        Property::Light,
+                        Map::parse(kind, map_lines)?,
                     ),
-                    kind if kind.starts_with("water-to-light map") => {
-                        output.insert_map(Property::Water, Property::Light, Map::parse(map_lines)?)
-                    }
                     kind if kind.starts_with("light-to-temperature map") => output.insert_map(
                         Property::Light,
                         Property::Temperature,
-                        Map::parse(map_lines)?,
+                        Map::parse(kind, map_lines)?,
                     ),
                     kind if kind.starts_with("temperature-to-humidity map") => output.insert_map(
                         Property::Temperature,
                         Property::Humidity,
-                        Map::parse(map_lines)?,
+                        Map::parse(kind, map_lines)?,
                     ),
                     kind if kind.starts_with("humidity-to-location map") => output.insert_map(
                         Property::Humidity,
                         Property::Location,
-                        Map::parse(map_lines)?,
+                        Map::parse(kind, map_lines)?,
                     ),
                     _ => {}
                 }
@@ -83,6 +87,21 @@ mod almanac {
         Location,
     }
 
+    impl Property {
+        pub fn name(&self) -> &'static str {
+            match self {
+                Self::Seed => "seed",
+                Self::Soil => "soil",
+                Self::Fertilizer => "fertilizer",
+                Self::Water => "water",
+                Self::Light => "light",
+                Self::Temperature => "temperature",
+                Self::Humidity => "humidity",
+                Self::Location => "location",
+            }
+        }
+    }
+
     impl Iterator for Property {
         type Item = Self;
 
@@ -103,29 +122,43 @@ mod almanac {
     #[derive(Debug, Hash, PartialEq, Eq)]
     struct Key(Property, Property);
 
+    /// Lines sorted by source start, so a value's line can be found by binary search.
     #[derive(Debug)]
     struct Map(Vec<MapLine>);
 
     impl Map {
-        fn parse(input: &str) -> Result<Self, Box<dyn std::error::Error>> {
-            let map_lines = input
+        /// `header` is the map's title line, e.g. `seed-to-soil map:`, named in errors.
+        fn parse(header: &str, input: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            let name = header.trim_end_matches(':');
+            let mut map_lines = input
                 .split("\n")
-                .map(|line| MapLine::parse(line))
+                .enumerate()
+                .map(|(i, line)| {
+                    MapLine::parse(line).map_err(|e| format!("{name} line {}: {e}", i + 1))
+                })
                 .collect::<Result<Vec<_>, _>>()?;
+            map_lines.sort_by_key(|line| line.src_start);
+            if let Some(pair) = map_lines
+                .windows(2)
+                .find(|pair| pair[0].src_start + pair[0].range_len > pair[1].src_start)
+            {
+                return Err(format!(
+                    "source ranges starting at {} and {} overlap",
+                    pair[0].src_start, pair[1].src_start
+                )
+                .into());
+            }
             Ok(Self(map_lines))
         }
 
         fn map_value(&self, value: u64) -> u64 {
-            self.0
-                .iter()
-                .find_map(|line| {
-                    if value >= line.src_start && value <= line.src_start + line.range_len {
-                        Some((line.dst_start + value) - line.src_start)
-                    } else {
-                        None
-                    }
-                })
-                .unwrap_or(value)
+            let index = self.0.partition_point(|line| line.src_start <= value);
+            match index.checked_sub(1).map(|index| &self.0[index]) {
+                Some(line) if value - line.src_start < line.range_len => {
+                    line.dst_start + (value - line.src_start)
+                }
+                _ => value,
+            }
         }
     }
 
@@ -143,6 +176,11 @@ mod almanac {
             let dst_start = iter.next().ok_or("no destination start in map line")??;
             let src_start = iter.next().ok_or("no source start in map line")??;
             let range_len = iter.next().ok_or("no range length in map line")??;
+            if dst_start.checked_add(range_len).is_none()
+                || src_start.checked_add(range_len).is_none()
+            {
+                return Err(format!("range of length {range_len} overflows").into());
+            }
             Ok(MapLine {
                 dst_start,
                 src_start,
@@ -150,74 +188,142 @@ mod almanac {
             })
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn range_end_is_exclusive() {
+            let map = Map::parse("seed-to-soil map:", "50 10 5").unwrap();
+            assert_eq!(map.map_value(9), 9);
+            assert_eq!(map.map_value(10), 50);
+            assert_eq!(map.map_value(14), 54);
+            assert_eq!(map.map_value(15), 15);
+        }
+
+        #[test]
+        fn overlapping_ranges() {
+            assert!(Map::parse("seed-to-soil map:", "0 10 5\n100 15 5").is_ok());
+            assert_eq!(
+                Map::parse("seed-to-soil map:", "100 14 5\n0 10 5")
+                    .unwrap_err()
+                    .to_string(),
+                "source ranges starting at 10 and 14 overlap"
+            );
+        }
+    }
 }
 
-pub mod part1 {
+pub mod repl {
     use super::*;
+    use crate::repl::{arg, unknown};
+    use almanac::Property;
 
-    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
-        let almanac = almanac::Almanac::parse(input)?;
-        let min_location = almanac
-            .seeds
-            .iter()
-            .map(|value| {
-                let mut property = almanac::Property::Seed;
-                let mut value = *value;
-                loop {
-                    if let Some(next_property) = property.next() {
-                        value = almanac.perform_map(property, next_property, value);
-                        property = next_property;
-                    } else {
-                        break;
-                    }
-                }
-                value
-            })
-            .min();
-        Ok(min_location.unwrap())
-    }
+    #[derive(Debug)]
+    pub struct Almanac(almanac::Almanac);
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
-        const EXAMPLE_1: &str = "
-            seeds: 79 14 55 13
+    impl Almanac {
+        pub fn parse(input: &str) -> Result<Self, Box<dyn std::error::Error>> {
+            Ok(Self(almanac::Almanac::parse(input)?))
+        }
+    }
 
-            seed-to-soil map:
-            50 98 2
-            52 50 48
+    fn property(name: &str) -> Result<Property, String> {
+        let mut property = Property::Seed;
+        while property.name() != name {
+            property = property
+                .next()
+                .ok_or_else(|| format!("unknown category {name}"))?;
+        }
+        Ok(property)
+    }
 
-            soil-to-fertilizer map:
-            0 15 37
-            37 52 2
-            39 0 15
+    impl crate::repl::Model for Almanac {
+        fn help(&self) -> &'static str {
+            "map <category> <value> <category>\nseeds"
+        }
 
-            fertilizer-to-water map:
-            49 53 8
-            0 11 42
-            42 0 7
-            57 7 4
+        fn query(&self, words: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+            match words {
+                ["seeds"] => Ok(self
+                    .0
+                    .seeds
+                    .iter()
+                    .map(u64::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" ")),
+                ["map", from, value, to] => {
+                    let (mut current, to) = (property(from)?, property(to)?);
+                    let mut value = arg::<u64>(value, "value")?;
+                    while current != to {
+                        let next = current
+                            .next()
+                            .ok_or_else(|| format!("{} comes before {}", words[3], words[1]))?;
+                        value = self.0.perform_map(current, next, value);
+                        current = next;
+                    }
+                    Ok(value.to_string())
+                }
+                _ => Err(unknown(words, self.help())),
+            }
+        }
+    }
+}
 
-            water-to-light map:
-            88 18 7
-            18 25 70
+pub mod part1 {
+    use super::*;
+    use crate::observe::{Event, Observer};
 
-            light-to-temperature map:
-            45 77 23
-            81 45 19
-            68 64 13
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        solve_observed(input, &())
+    }
 
-            temperature-to-humidity map:
-            0 69 1
-            1 0 69
+    pub fn solve_observed<O: Observer + ?Sized>(
+        input: &str,
+        observer: &O,
+    ) -> Result<u64, Box<dyn std::error::Error>> {
+        let almanac = almanac::Almanac::parse(input)?;
+        let Ok(locations) = crate::par::map_items(&almanac.seeds, |seed| {
+            let mut property = almanac::Property::Seed;
+            let mut value = *seed;
+            while let Some(next_property) = property.next() {
+                let mapped = almanac.perform_map(property, next_property, value);
+                observer.event(Event::MapHop {
+                    seed: *seed,
+                    from: property.name(),
+                    to: next_property.name(),
+                    value,
+                    mapped,
+                });
+                value = mapped;
+                property = next_property;
+            }
+            Ok::<_, std::convert::Infallible>(value)
+        });
+        Ok(locations.into_iter().min().unwrap())
+    }
 
-            humidity-to-location map:
-            60 56 37
-            56 93 4";
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        const EXAMPLE_1: &str = include_str!("../data/examples/5-1.txt");
 
         #[test]
         fn solve_example_1() {
             assert_eq!(solve(EXAMPLE_1).unwrap(), 35);
         }
+
+        #[test]
+        fn overflow() {
+            let input = format!(
+                "seeds: 1\n\nseed-to-soil map:\n0 0 1\n\nsoil-to-fertilizer map:\n0 0 1\n{} 2 2",
+                u64::MAX
+            );
+            assert_eq!(
+                solve(&input).unwrap_err().to_string(),
+                "soil-to-fertilizer map line 2: range of length 2 overflows"
+            );
+        }
     }
 }
@@ -0,0 +1,151 @@
+#[derive(Debug, PartialEq, Eq)]
+struct Race {
+    time: u64,
+    distance: u64,
+}
+
+impl Race {
+    /// Holding for `h` of `time` milliseconds travels `h * (time - h)`, so the
+    /// winning holds lie strictly between the roots of
+    /// `h^2 - time * h + distance = 0`. The lower root is estimated with an
+    /// integer square root and nudged onto the first winning hold; the range
+    /// is symmetric around `time / 2`.
+    fn winning_holds(&self) -> u64 {
+        let (time, distance) = (self.time as u128, self.distance as u128);
+        let travelled = |hold: u128| hold * (time - hold);
+        let Some(discriminant) = (time * time).checked_sub(4 * distance) else {
+            return 0;
+        };
+        let mut low = (time - discriminant.isqrt()) / 2;
+        while low > 0 && travelled(low - 1) > distance {
+            low -= 1;
+        }
+        while low <= time / 2 && travelled(low) <= distance {
+            low += 1;
+        }
+        if low > time / 2 {
+            0
+        } else {
+            (time - 2 * low + 1) as u64
+        }
+    }
+}
+
+fn parse_line<'a>(line: Option<&'a str>, name: &str) -> Result<&'a str, String> {
+    line.and_then(|line| line.strip_prefix(name))
+        .and_then(|line| line.strip_prefix(':'))
+        .ok_or_else(|| format!("no {name} line"))
+}
+
+fn parse_races(input: &str) -> Result<Vec<Race>, Box<dyn std::error::Error>> {
+    let mut lines = input.lines();
+    let times = parse_line(lines.next(), "Time")?;
+    let distances = parse_line(lines.next(), "Distance")?;
+    let times = times
+        .split_whitespace()
+        .map(|n| n.parse())
+        .collect::<Result<Vec<_>, _>>()?;
+    let distances = distances
+        .split_whitespace()
+        .map(|n| n.parse())
+        .collect::<Result<Vec<_>, _>>()?;
+    if times.len() != distances.len() {
+        return Err(format!("{} times but {} distances", times.len(), distances.len()).into());
+    }
+    Ok(times
+        .into_iter()
+        .zip(distances)
+        .map(|(time, distance)| Race { time, distance })
+        .collect())
+}
+
+pub mod part2 {
+    use super::*;
+
+    /// Reads each line as one number, ignoring the spaces between digits.
+    fn parse_race(input: &str) -> Result<Race, Box<dyn std::error::Error>> {
+        let mut lines = input.lines();
+        let mut number = |name| -> Result<u64, Box<dyn std::error::Error>> {
+            let digits = parse_line(lines.next(), name)?
+                .split_whitespace()
+                .collect::<String>();
+            Ok(digits.parse()?)
+        };
+        Ok(Race {
+            time: number("Time")?,
+            distance: number("Distance")?,
+        })
+    }
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        Ok(parse_race(input)?.winning_holds())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/6-1.txt");
+
+        #[test]
+        fn parse_example_1() {
+            assert_eq!(
+                parse_race(EXAMPLE_1).unwrap(),
+                Race {
+                    time: 71530,
+                    distance: 940200
+                }
+            );
+        }
+
+        #[test]
+        fn solve_example_1() {
+            assert_eq!(solve(EXAMPLE_1).unwrap(), 71503);
+        }
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        parse_races(input)?.iter().try_fold(1u64, |acc, race| {
+            acc.checked_mul(race.winning_holds())
+                .ok_or_else(|| "product of winning holds overflows".into())
+        })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/6-1.txt");
+
+        #[test]
+        fn solve_example_1() {
+            assert_eq!(solve(EXAMPLE_1).unwrap(), 288);
+        }
+
+        #[test]
+        fn winning_holds_match_brute_force() {
+            for time in 0..60 {
+                for distance in 0..time * time / 4 + 3 {
+                    let race = Race { time, distance };
+                    let expected = (0..=time)
+                        .filter(|hold| hold * (time - hold) > distance)
+                        .count() as u64;
+                    assert_eq!(race.winning_holds(), expected, "{race:?}");
+                }
+            }
+        }
+
+        #[test]
+        fn huge_race() {
+            let race = Race {
+                time: u64::MAX,
+                distance: u64::MAX,
+            };
+            assert_eq!(race.winning_holds(), u64::MAX - 3);
+        }
+    }
+}
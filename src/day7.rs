@@ -0,0 +1,245 @@
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+/// The cards of a game, weakest first, and optionally one that stands in for any other.
+pub trait Alphabet {
+    const CARDS: &'static str;
+    const WILD: Option<char> = None;
+
+    fn strength(card: char) -> Option<usize> {
+        Self::CARDS.find(card)
+    }
+}
+
+/// Camel Cards as first dealt, `2` up to `A`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Standard;
+
+impl Alphabet for Standard {
+    const CARDS: &'static str = "23456789TJQKA";
+}
+
+/// `J` is a joker: wild for the hand type and the weakest card in ties.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Jokers;
+
+impl Alphabet for Jokers {
+    const CARDS: &'static str = "J23456789TQKA";
+    const WILD: Option<char> = Some('J');
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandType {
+    /// Wild cards always do best joining the largest group of equal cards.
+    fn classify<A: Alphabet>(cards: &str) -> Self {
+        let mut counts = vec![0; A::CARDS.len()];
+        let mut wild = 0;
+        for card in cards.chars() {
+            match A::strength(card) {
+                _ if Some(card) == A::WILD => wild += 1,
+                Some(strength) => counts[strength] += 1,
+                None => {}
+            }
+        }
+        counts.sort_by(|a, b| b.cmp(a));
+        match (counts[0] + wild, counts.get(1).copied().unwrap_or_default()) {
+            (5.., _) => HandType::FiveOfAKind,
+            (4, _) => HandType::FourOfAKind,
+            (3, 2) => HandType::FullHouse,
+            (3, _) => HandType::ThreeOfAKind,
+            (2, 2) => HandType::TwoPair,
+            (2, _) => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+}
+
+/// Hands order by type, then card by card from the first.
+#[derive(Debug, Clone)]
+pub struct Hand<A> {
+    cards: String,
+    strengths: Vec<usize>,
+    kind: HandType,
+    alphabet: PhantomData<A>,
+}
+
+impl<A: Alphabet> Hand<A> {
+    pub fn parse(cards: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let strengths = cards
+            .chars()
+            .map(|card| A::strength(card).ok_or_else(|| format!("{card} is not a card")))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            cards: cards.to_string(),
+            strengths,
+            kind: HandType::classify::<A>(cards),
+            alphabet: PhantomData,
+        })
+    }
+
+    pub fn cards(&self) -> &str {
+        &self.cards
+    }
+
+    pub fn kind(&self) -> HandType {
+        self.kind
+    }
+}
+
+impl<A> PartialEq for Hand<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<A> Eq for Hand<A> {}
+
+impl<A> PartialOrd for Hand<A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<A> Ord for Hand<A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.kind, &self.strengths).cmp(&(other.kind, &other.strengths))
+    }
+}
+
+/// Each hand with its bid.
+type Bids<A> = Vec<(Hand<A>, u64)>;
+
+fn parse_bids<A: Alphabet>(input: &str) -> Result<Bids<A>, Box<dyn std::error::Error>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let (cards, bid) = line
+                .split_once(' ')
+                .ok_or_else(|| format!("line {i}: expected a hand and a bid"))?;
+            if cards.chars().count() != 5 {
+                return Err(format!("line {i}: hand {cards} does not have 5 cards").into());
+            }
+            let hand = Hand::parse(cards).map_err(|e| format!("line {i}: {e}"))?;
+            Ok((hand, bid.trim().parse()?))
+        })
+        .collect()
+}
+
+/// Each bid multiplied by its hand's rank, the weakest hand ranking 1.
+pub fn total_winnings<A: Alphabet>(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+    let mut bids = parse_bids::<A>(input)?;
+    bids.sort_by(|(a, _), (b, _)| a.cmp(b));
+    bids.iter().zip(1..).try_fold(
+        0u64,
+        |acc, ((hand, bid), rank)| -> Result<u64, Box<dyn std::error::Error>> {
+            bid.checked_mul(rank)
+                .and_then(|winnings| acc.checked_add(winnings))
+                .ok_or_else(|| format!("winnings overflow at hand {}", hand.cards()).into())
+        },
+    )
+}
+
+pub mod part2 {
+    use super::*;
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        total_winnings::<Jokers>(input)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/7-1.txt");
+
+        #[test]
+        fn solve_example_1() {
+            assert_eq!(solve(EXAMPLE_1).unwrap(), 5905);
+        }
+
+        #[test]
+        fn jokers() {
+            let kind = |cards| Hand::<Jokers>::parse(cards).unwrap().kind();
+            assert_eq!(kind("T55J5"), HandType::FourOfAKind);
+            assert_eq!(kind("KTJJT"), HandType::FourOfAKind);
+            assert_eq!(kind("JJJJJ"), HandType::FiveOfAKind);
+            assert_eq!(kind("2233J"), HandType::FullHouse);
+            assert_eq!(kind("2345J"), HandType::OnePair);
+            // a joker is the weakest card when breaking ties
+            assert!(Hand::<Jokers>::parse("JKKK2").unwrap() < Hand::parse("QQQQ2").unwrap());
+        }
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        total_winnings::<Standard>(input)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/7-1.txt");
+
+        #[test]
+        fn solve_example_1() {
+            assert_eq!(solve(EXAMPLE_1).unwrap(), 6440);
+        }
+
+        #[test]
+        fn classify() {
+            let kind = |cards| Hand::<Standard>::parse(cards).unwrap().kind();
+            assert_eq!(kind("AAAAA"), HandType::FiveOfAKind);
+            assert_eq!(kind("AA8AA"), HandType::FourOfAKind);
+            assert_eq!(kind("23332"), HandType::FullHouse);
+            assert_eq!(kind("TTT98"), HandType::ThreeOfAKind);
+            assert_eq!(kind("23432"), HandType::TwoPair);
+            assert_eq!(kind("A23A4"), HandType::OnePair);
+            assert_eq!(kind("23456"), HandType::HighCard);
+            assert_eq!(kind("T55J5"), HandType::ThreeOfAKind);
+        }
+
+        #[test]
+        fn ordering() {
+            let hand = |cards| Hand::<Standard>::parse(cards).unwrap();
+            assert!(hand("33332") > hand("2AAAA"));
+            assert!(hand("77888") > hand("77788"));
+            assert!(hand("KK677") > hand("KTJJT"));
+            assert_eq!(
+                parse_bids::<Standard>("32T3 765").unwrap_err().to_string(),
+                "line 0: hand 32T3 does not have 5 cards"
+            );
+            assert_eq!(
+                parse_bids::<Standard>("32T3X 765").unwrap_err().to_string(),
+                "line 0: X is not a card"
+            );
+        }
+
+        /// Other games only need their own alphabet.
+        #[test]
+        fn custom_alphabet() {
+            struct Dice;
+            impl Alphabet for Dice {
+                const CARDS: &'static str = "123456";
+            }
+            let hand = |cards| Hand::<Dice>::parse(cards).unwrap();
+            assert_eq!(hand("66611").kind(), HandType::FullHouse);
+            assert!(hand("11112") > hand("66651"));
+            assert!(Hand::<Dice>::parse("7").is_err());
+        }
+    }
+}
@@ -0,0 +1,413 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Left,
+    Right,
+}
+
+#[derive(Debug)]
+struct Network {
+    instructions: Vec<Direction>,
+    names: Vec<String>,
+    /// Left and right neighbour of each node, by index into `names`.
+    edges: Vec<(usize, usize)>,
+}
+
+impl Network {
+    fn parse(input: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let (instructions, nodes) = input
+            .split_once("\n\n")
+            .ok_or("instructions must be followed by a blank line")?;
+        let instructions = instructions
+            .trim()
+            .chars()
+            .map(|c| match c {
+                'L' => Ok(Direction::Left),
+                'R' => Ok(Direction::Right),
+                other => Err(format!("invalid instruction {other}")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if instructions.is_empty() {
+            return Err("no instructions".into());
+        }
+        let lines = nodes
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let (name, next) = line
+                    .split_once(" = ")
+                    .ok_or_else(|| format!("expected `NODE = (LEFT, RIGHT)`, found {line:?}"))?;
+                let (left, right) = next
+                    .trim()
+                    .strip_prefix('(')
+                    .and_then(|next| next.strip_suffix(')'))
+                    .and_then(|next| next.split_once(", "))
+                    .ok_or_else(|| format!("expected `(LEFT, RIGHT)`, found {next:?}"))?;
+                Ok((name.trim(), left, right))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let index = lines
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _, _))| (*name, i))
+            .collect::<HashMap<_, _>>();
+        let lookup = |name: &str| {
+            index
+                .get(name)
+                .copied()
+                .ok_or_else(|| format!("node {name} is not defined"))
+        };
+        let edges = lines
+            .iter()
+            .map(|(_, left, right)| Ok((lookup(left)?, lookup(right)?)))
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Self {
+            instructions,
+            names: lines.iter().map(|(name, _, _)| name.to_string()).collect(),
+            edges,
+        })
+    }
+
+    fn node(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    fn step(&self, node: usize, step: usize) -> usize {
+        match self.instructions[step % self.instructions.len()] {
+            Direction::Left => self.edges[node].0,
+            Direction::Right => self.edges[node].1,
+        }
+    }
+}
+
+/// One walker's path. The walk is fixed by its node and its position in the
+/// instructions, so after `tail` steps it repeats every `period` steps.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Ghost {
+    pub start: String,
+    pub tail: u64,
+    pub period: u64,
+    /// Steps before the walk first repeats that end on a finishing node.
+    pub finishes: Vec<u64>,
+}
+
+impl Ghost {
+    fn walk<T>(network: &Network, start: usize, is_finish: T) -> Self
+    where
+        T: Fn(&str) -> bool,
+    {
+        let len = network.instructions.len();
+        let mut seen = HashMap::new();
+        let mut finishes = Vec::new();
+        let mut node = start;
+        let mut step = 0;
+        let tail = loop {
+            if let Some(first) = seen.insert((node, step % len), step) {
+                break first;
+            }
+            if is_finish(&network.names[node]) {
+                finishes.push(step as u64);
+            }
+            node = network.step(node, step);
+            step += 1;
+        };
+        Self {
+            start: network.names[start].clone(),
+            tail: tail as u64,
+            period: (step - tail) as u64,
+            finishes,
+        }
+    }
+
+    fn is_finished(&self, step: u64) -> bool {
+        let step = if step < self.tail + self.period {
+            step
+        } else {
+            self.tail + (step - self.tail) % self.period
+        };
+        self.finishes.contains(&step)
+    }
+
+    /// Why this walk breaks the assumption behind taking the LCM of the
+    /// periods: that it finishes exactly once per cycle, on the step that
+    /// completes its first cycle.
+    pub fn lcm_violation(&self) -> Option<String> {
+        let start = &self.start;
+        match self.finishes[..] {
+            [] => Some(format!("the ghost from {start} never reaches a Z node")),
+            [finish] if finish < self.tail => Some(format!(
+                "the ghost from {start} only reaches a Z node on step {finish}, \
+                 before its walk starts repeating"
+            )),
+            [finish] if finish == self.period => None,
+            [finish] => Some(format!(
+                "the ghost from {start} first reaches a Z node on step {finish} \
+                 but its walk repeats every {} steps",
+                self.period
+            )),
+            ref finishes => Some(format!(
+                "the ghost from {start} reaches {} Z nodes before its walk repeats",
+                finishes.len()
+            )),
+        }
+    }
+}
+
+pub fn ghosts(input: &str) -> Result<Vec<Ghost>, Box<dyn std::error::Error>> {
+    let network = Network::parse(input)?;
+    Ok((0..network.names.len())
+        .filter(|&node| network.names[node].ends_with('A'))
+        .map(|node| Ghost::walk(&network, node, |name| name.ends_with('Z')))
+        .collect())
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Combines `x ≡ a (mod m)` and `x ≡ b (mod n)` into one congruence, or
+/// `None` when they have no common solution.
+fn crt((a, m): (u128, u128), (b, n): (u128, u128)) -> Option<(u128, u128)> {
+    let g = gcd(m, n);
+    let (a, b) = (a % m, b % n);
+    if a % g != b % g {
+        return None;
+    }
+    // x = a + m * k, with m * k ≡ b - a (mod n)
+    let (m_g, n_g) = (m / g, n / g);
+    let diff = (b + n - a % n) % n / g;
+    let k = diff * inverse(m_g % n_g, n_g) % n_g;
+    let lcm = m.checked_mul(n_g)?;
+    Some(((a + m * k) % lcm, lcm))
+}
+
+fn inverse(a: u128, m: u128) -> u128 {
+    if m == 1 {
+        return 0;
+    }
+    let (mut old_r, mut r) = (a as i128, m as i128);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+    }
+    old_s.rem_euclid(m as i128) as u128
+}
+
+pub mod part2 {
+    use super::*;
+
+    /// Fast path when every ghost meets the LCM assumption, see [`Ghost::lcm_violation`].
+    fn lcm(ghosts: &[Ghost]) -> Result<u64, Box<dyn std::error::Error>> {
+        ghosts.iter().try_fold(1u64, |acc, ghost| {
+            (acc / gcd(acc as u128, ghost.period as u128) as u64)
+                .checked_mul(ghost.period)
+                .ok_or_else(|| "the number of steps overflows".into())
+        })
+    }
+
+    /// The first step on which every ghost stands on a Z node, in general:
+    /// steps before all walks repeat are checked directly, after that each
+    /// ghost finishes on fixed residues of its period, combined by CRT.
+    fn general(ghosts: &[Ghost]) -> Option<u64> {
+        let settled = ghosts.iter().map(|ghost| ghost.tail).max()?;
+        if let Some(step) =
+            (1..settled).find(|&step| ghosts.iter().all(|ghost| ghost.is_finished(step)))
+        {
+            return Some(step);
+        }
+        let mut best: Option<u128> = None;
+        let mut choices = vec![(0u128, 1u128)];
+        for ghost in ghosts {
+            let residues = ghost
+                .finishes
+                .iter()
+                .filter(|&&finish| finish >= ghost.tail)
+                .map(|&finish| (finish as u128, ghost.period as u128))
+                .collect::<Vec<_>>();
+            choices = choices
+                .iter()
+                .flat_map(|&choice| residues.iter().filter_map(move |&r| crt(choice, r)))
+                .collect();
+        }
+        for (residue, modulus) in choices {
+            let settled = settled.max(1) as u128;
+            let step = if residue >= settled {
+                residue
+            } else {
+                residue + (settled - residue).div_ceil(modulus) * modulus
+            };
+            best = Some(best.map_or(step, |best| best.min(step)));
+        }
+        best.and_then(|step| step.try_into().ok())
+    }
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        let ghosts = ghosts(input)?;
+        if ghosts.is_empty() {
+            return Err("no node ends in A".into());
+        }
+        let violations = ghosts
+            .iter()
+            .filter_map(Ghost::lcm_violation)
+            .collect::<Vec<_>>();
+        if violations.is_empty() {
+            return lcm(&ghosts);
+        }
+        general(&ghosts).ok_or_else(|| {
+            format!(
+                "the ghosts are never all on Z nodes at once, and the LCM shortcut does not \
+                 apply because {}",
+                violations.join(", and ")
+            )
+            .into()
+        })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_3: &str = include_str!("../data/examples/8-3.txt");
+
+        #[test]
+        fn solve_example_3() {
+            assert_eq!(solve(EXAMPLE_3).unwrap(), 6);
+        }
+
+        #[test]
+        fn cycles() {
+            let ghosts = ghosts(EXAMPLE_3).unwrap();
+            assert_eq!(
+                ghosts,
+                vec![
+                    Ghost {
+                        start: "11A".to_string(),
+                        tail: 1,
+                        period: 2,
+                        finishes: vec![2],
+                    },
+                    Ghost {
+                        start: "22A".to_string(),
+                        tail: 1,
+                        period: 6,
+                        finishes: vec![3, 6],
+                    },
+                ]
+            );
+            assert_eq!(ghosts[0].lcm_violation(), None);
+            assert_eq!(
+                ghosts[1].lcm_violation().unwrap(),
+                "the ghost from 22A reaches 2 Z nodes before its walk repeats"
+            );
+        }
+
+        #[test]
+        fn lcm_shortcut() {
+            // periods 2 and 3, each finishing once at the end of its cycle
+            let input = "L\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11B, 11B)\n\
+                         22A = (22B, 22B)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)";
+            let ghosts = ghosts(input).unwrap();
+            assert!(ghosts.iter().all(|ghost| ghost.lcm_violation().is_none()));
+            assert_eq!(lcm(&ghosts).unwrap(), 6);
+            assert_eq!(general(&ghosts), Some(6));
+        }
+
+        #[test]
+        fn explains_failure() {
+            // both ghosts cycle every 2 steps, but out of phase
+            let input = "L\n\n11A = (11Z, 11Z)\n11Z = (11A, 11A)\n\
+                         22A = (22B, 22B)\n22B = (22Z, 22Z)\n22Z = (22B, 22B)";
+            assert_eq!(
+                solve(input).unwrap_err().to_string(),
+                "the ghosts are never all on Z nodes at once, and the LCM shortcut does not \
+                 apply because the ghost from 11A first reaches a Z node on step 1 but its walk \
+                 repeats every 2 steps"
+            );
+        }
+
+        #[test]
+        fn rejects_finish_in_tail() {
+            // 11A only passes 11Z on step 2, before settling into 11C <-> 11D
+            let input = "L\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11C, 11C)\n\
+                         11C = (11D, 11D)\n11D = (11C, 11C)\n\
+                         22A = (22B, 22B)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n\
+                         22Z = (22B, 22B)";
+            assert_eq!(
+                solve(input).unwrap_err().to_string(),
+                "the ghosts are never all on Z nodes at once, and the LCM shortcut does not \
+                 apply because the ghost from 11A only reaches a Z node on step 2, before its \
+                 walk starts repeating"
+            );
+        }
+
+        #[test]
+        fn crt_matches_brute_force() {
+            for m in 1..12u128 {
+                for n in 1..12u128 {
+                    for a in 0..m {
+                        for b in 0..n {
+                            let expected = (0..m * n).find(|x| x % m == a && x % n == b);
+                            assert_eq!(crt((a, m), (b, n)).map(|(x, _)| x), expected);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn solve(input: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        let network = Network::parse(input)?;
+        let start = network.node("AAA").ok_or("no node AAA")?;
+        let ghost = Ghost::walk(&network, start, |name| name == "ZZZ");
+        ghost
+            .finishes
+            .first()
+            .copied()
+            .ok_or_else(|| "ZZZ cannot be reached from AAA".into())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/8-1.txt");
+        const EXAMPLE_2: &str = include_str!("../data/examples/8-2.txt");
+
+        #[test]
+        fn solve_example_1() {
+            assert_eq!(solve(EXAMPLE_1).unwrap(), 2);
+        }
+
+        #[test]
+        fn solve_example_2() {
+            assert_eq!(solve(EXAMPLE_2).unwrap(), 6);
+        }
+
+        #[test]
+        fn parse_errors() {
+            assert_eq!(
+                solve("LR\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)")
+                    .unwrap_err()
+                    .to_string(),
+                "node BBB is not defined"
+            );
+            assert_eq!(
+                solve("L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)")
+                    .unwrap_err()
+                    .to_string(),
+                "ZZZ cannot be reached from AAA"
+            );
+        }
+    }
+}
@@ -0,0 +1,109 @@
+fn parse_histories(input: &str) -> Result<Vec<Vec<i64>>, Box<dyn std::error::Error>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let history = line
+                .split_whitespace()
+                .map(|n| n.parse::<i64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("line {i}: {e}"))?;
+            if history.is_empty() {
+                return Err(format!("line {i} has no values").into());
+            }
+            Ok(history)
+        })
+        .collect()
+}
+
+/// The next value, found by differencing until every difference is zero and
+/// then adding the last value of each row back up. `None` on overflow.
+fn extrapolate(history: &[i64]) -> Option<i64> {
+    let mut row = history.to_vec();
+    let mut next = 0i64;
+    while row.iter().any(|n| *n != 0) {
+        next = next.checked_add(*row.last()?)?;
+        row = row
+            .windows(2)
+            .map(|pair| pair[1].checked_sub(pair[0]))
+            .collect::<Option<_>>()?;
+    }
+    Some(next)
+}
+
+fn sum_extrapolated<'a, T>(histories: T) -> Result<i64, Box<dyn std::error::Error>>
+where
+    T: Iterator<Item = &'a [i64]>,
+{
+    histories.enumerate().try_fold(
+        0i64,
+        |acc, (i, history)| -> Result<i64, Box<dyn std::error::Error>> {
+            extrapolate(history)
+                .and_then(|next| acc.checked_add(next))
+                .ok_or_else(|| format!("line {i} overflows").into())
+        },
+    )
+}
+
+pub mod part2 {
+    use super::*;
+
+    /// Extrapolating backwards is extrapolating the reversed history forwards.
+    pub fn solve(input: &str) -> Result<i64, Box<dyn std::error::Error>> {
+        let mut histories = parse_histories(input)?;
+        for history in histories.iter_mut() {
+            history.reverse();
+        }
+        sum_extrapolated(histories.iter().map(Vec::as_slice))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/9-1.txt");
+
+        #[test]
+        fn solve_example_1() {
+            assert_eq!(solve(EXAMPLE_1).unwrap(), 2);
+        }
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn solve(input: &str) -> Result<i64, Box<dyn std::error::Error>> {
+        sum_extrapolated(parse_histories(input)?.iter().map(Vec::as_slice))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE_1: &str = include_str!("../data/examples/9-1.txt");
+
+        #[test]
+        fn solve_example_1() {
+            assert_eq!(solve(EXAMPLE_1).unwrap(), 114);
+        }
+
+        #[test]
+        fn extrapolate_examples() {
+            assert_eq!(extrapolate(&[0, 3, 6, 9, 12, 15]), Some(18));
+            assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45]), Some(68));
+            assert_eq!(extrapolate(&[5]), Some(5));
+            assert_eq!(extrapolate(&[-4, -8, -12]), Some(-16));
+            assert_eq!(extrapolate(&[i64::MIN, i64::MAX]), None);
+        }
+
+        #[test]
+        fn negative_answer() {
+            assert_eq!(solve("3 1 -1\n-5 -5").unwrap(), -8);
+            assert_eq!(
+                solve("1 2\n1 x").unwrap_err().to_string(),
+                "line 1: invalid digit found in string"
+            );
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::path::{Path, PathBuf};
+
+/// A puzzle example stored as `data/examples/N-k.txt`, with the answers it
+/// should give in `N-k.expected` as `part: answer` lines.
+#[derive(Debug, Clone)]
+pub struct Example {
+    pub name: String,
+    pub path: PathBuf,
+    pub input: String,
+    answers: Vec<(u8, String)>,
+}
+
+impl Example {
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        self.answers
+            .iter()
+            .find(|(p, _)| *p == part)
+            .map(|(_, answer)| answer.as_str())
+    }
+
+    fn parse_answers(input: &str) -> Result<Vec<(u8, String)>, Box<dyn std::error::Error>> {
+        input
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let (part, answer) = line
+                    .split_once(":")
+                    .ok_or_else(|| format!("expected `part: answer`, found {line:?}"))?;
+                Ok((part.trim().parse()?, answer.trim().to_string()))
+            })
+            .collect()
+    }
+}
+
+pub fn dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("data/examples")
+}
+
+/// Every example for a day, ordered by number.
+pub fn load(day: u8) -> Result<Vec<Example>, Box<dyn std::error::Error>> {
+    let prefix = format!("{day}-");
+    let mut examples = Vec::new();
+    for entry in std::fs::read_dir(dir())? {
+        let path = entry?.path();
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let Some(number) = name.strip_prefix(&prefix) else {
+            continue;
+        };
+        if path.extension().is_none_or(|extension| extension != "txt") {
+            continue;
+        }
+        let number = number.parse::<u32>()?;
+        let answers = match std::fs::read_to_string(path.with_extension("expected")) {
+            Ok(answers) => Example::parse_answers(&answers)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        examples.push((
+            number,
+            Example {
+                name: name.to_string(),
+                input: std::fs::read_to_string(&path)?,
+                path,
+                answers,
+            },
+        ));
+    }
+    examples.sort_by_key(|(number, _)| *number);
+    Ok(examples.into_iter().map(|(_, example)| example).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_day_1() {
+        let examples = load(1).unwrap();
+        assert_eq!(
+            examples
+                .iter()
+                .map(|example| example.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["1-1", "1-2"]
+        );
+        assert_eq!(examples[0].expected(1), Some("142"));
+        assert_eq!(examples[1].expected(1), None);
+        assert_eq!(examples[1].expected(2), Some("281"));
+    }
+}
@@ -0,0 +1,409 @@
+use std::fmt::Write;
+
+/// SplitMix64, so that the same seed always produces the same input.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..n`, or 0 when `n` is 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        if n == 0 {
+            0
+        } else {
+            self.next_u64() % n
+        }
+    }
+
+    /// Uniform value in `low..=high`.
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        low + self.below(high - low + 1)
+    }
+
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+
+    /// Index picked with probability proportional to its weight.
+    pub fn weighted(&mut self, weights: &[u64]) -> usize {
+        let mut pick = self.below(weights.iter().sum());
+        for (i, weight) in weights.iter().enumerate() {
+            if pick < *weight {
+                return i;
+            }
+            pick -= weight;
+        }
+        weights.len().saturating_sub(1)
+    }
+}
+
+pub mod day1 {
+    use super::*;
+
+    const WORDS: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+
+    #[derive(Debug, Clone)]
+    pub struct Options {
+        pub lines: usize,
+        /// Chance that each inserted digit is spelled out rather than numeric.
+        pub spelled: f64,
+    }
+
+    impl Default for Options {
+        fn default() -> Self {
+            Self {
+                lines: 1000,
+                spelled: 0.5,
+            }
+        }
+    }
+
+    /// Every line keeps at least one numeric digit so both parts can solve it.
+    pub fn generate(rng: &mut Rng, options: &Options) -> String {
+        let mut output = String::new();
+        for _ in 0..options.lines {
+            let digits = rng.between(1, 6);
+            let numeric = rng.below(digits);
+            for i in 0..digits {
+                for _ in 0..rng.below(6) {
+                    output.push((b'a' + rng.below(26) as u8) as char);
+                }
+                let digit = rng.between(1, 9) as usize;
+                if i != numeric && rng.chance(options.spelled) {
+                    output.push_str(WORDS[digit - 1]);
+                } else {
+                    write!(output, "{digit}").expect("writing to string");
+                }
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+pub mod day2 {
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    pub struct Options {
+        pub games: usize,
+        pub max_rounds: u64,
+        pub max_cubes: u64,
+    }
+
+    impl Default for Options {
+        fn default() -> Self {
+            Self {
+                games: 100,
+                max_rounds: 6,
+                max_cubes: 20,
+            }
+        }
+    }
+
+    pub fn generate(rng: &mut Rng, options: &Options) -> String {
+        let mut output = String::new();
+        for id in 1..=options.games {
+            write!(output, "Game {id}:").expect("writing to string");
+            for round in 0..rng.between(1, options.max_rounds.max(1)) {
+                let mut colours = ["red", "green", "blue"];
+                rng.shuffle(&mut colours);
+                let shown = rng.between(1, 3) as usize;
+                for (i, colour) in colours[..shown].iter().enumerate() {
+                    let separator = match (round, i) {
+                        (0, 0) => " ",
+                        (_, 0) => "; ",
+                        _ => ", ",
+                    };
+                    let count = rng.between(1, options.max_cubes.max(1));
+                    write!(output, "{separator}{count} {colour}").expect("writing to string");
+                }
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+pub mod day3 {
+    use super::*;
+
+    const SYMBOLS: &[u8] = b"#$%&+-/=@";
+
+    #[derive(Debug, Clone)]
+    pub struct Options {
+        pub width: usize,
+        pub height: usize,
+        /// Chance that a free cell starts a number.
+        pub part_density: f64,
+        /// Chance that a cell touching exactly two numbers becomes a gear.
+        pub gear_density: f64,
+        /// Chance that any other cell touching a number becomes a symbol.
+        pub symbol_density: f64,
+    }
+
+    impl Default for Options {
+        fn default() -> Self {
+            Self {
+                width: 140,
+                height: 140,
+                part_density: 0.08,
+                gear_density: 0.3,
+                symbol_density: 0.1,
+            }
+        }
+    }
+
+    pub fn generate(rng: &mut Rng, options: &Options) -> String {
+        let (width, height) = (options.width, options.height);
+        let mut grid = vec![vec![b'.'; width]; height];
+        let mut owner = vec![vec![None; width]; height];
+        let mut numbers = 0;
+        for i in 0..height {
+            let mut j = 0;
+            while j < width {
+                let len = rng.between(1, 3) as usize;
+                if j + len <= width && rng.chance(options.part_density) {
+                    grid[i][j] = b'1' + rng.below(9) as u8;
+                    for cell in grid[i][j + 1..j + len].iter_mut() {
+                        *cell = b'0' + rng.below(10) as u8;
+                    }
+                    for cell in owner[i][j..j + len].iter_mut() {
+                        *cell = Some(numbers);
+                    }
+                    numbers += 1;
+                    // leave a gap so neighbouring numbers don't merge
+                    j += len + 1;
+                } else {
+                    j += 1;
+                }
+            }
+        }
+        for (i, row) in grid.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                if *cell != b'.' {
+                    continue;
+                }
+                let mut touching = (i.saturating_sub(1)..(i + 2).min(height))
+                    .flat_map(|y| (j.saturating_sub(1)..(j + 2).min(width)).map(move |x| (y, x)))
+                    .filter_map(|(y, x)| owner[y][x])
+                    .collect::<Vec<_>>();
+                touching.sort();
+                touching.dedup();
+                if touching.len() == 2 && rng.chance(options.gear_density) {
+                    *cell = b'*';
+                } else if !touching.is_empty() && rng.chance(options.symbol_density) {
+                    *cell = *rng.choose(SYMBOLS);
+                }
+            }
+        }
+        grid.into_iter()
+            .map(|row| String::from_utf8(row).expect("grid is ascii") + "\n")
+            .collect()
+    }
+}
+
+pub mod day4 {
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    pub struct Options {
+        pub cards: usize,
+        pub winning: usize,
+        pub player: usize,
+        /// Relative weight of a card having 0, 1, 2, ... matches.
+        pub match_weights: Vec<u64>,
+    }
+
+    impl Default for Options {
+        fn default() -> Self {
+            Self {
+                cards: 200,
+                winning: 10,
+                player: 25,
+                match_weights: vec![8, 4, 2, 2, 1, 1, 1, 1, 1, 1, 1],
+            }
+        }
+    }
+
+    /// Numbers are drawn from 1..=99 so cards look like the real ones.
+    pub fn generate(rng: &mut Rng, options: &Options) -> String {
+        let winning = options.winning.min(99);
+        let player = options.player.min(99);
+        let mut output = String::new();
+        for id in 1..=options.cards {
+            let mut pool = (1..=99).collect::<Vec<u64>>();
+            rng.shuffle(&mut pool);
+            let matches = rng
+                .weighted(&options.match_weights)
+                .min(winning)
+                .min(player)
+                .max(player.saturating_sub(99 - winning));
+            let winning_numbers = &pool[..winning];
+            let mut player_numbers = winning_numbers[..matches].to_vec();
+            player_numbers.extend(pool[winning..].iter().take(player - matches));
+            rng.shuffle(&mut player_numbers);
+            write!(output, "Card {id:>3}:").expect("writing to string");
+            for n in winning_numbers {
+                write!(output, " {n:>2}").expect("writing to string");
+            }
+            output.push_str(" |");
+            for n in &player_numbers {
+                write!(output, " {n:>2}").expect("writing to string");
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+pub mod day5 {
+    use super::*;
+
+    const CATEGORIES: [&str; 8] = [
+        "seed",
+        "soil",
+        "fertilizer",
+        "water",
+        "light",
+        "temperature",
+        "humidity",
+        "location",
+    ];
+
+    #[derive(Debug, Clone)]
+    pub struct Options {
+        pub seeds: usize,
+        pub ranges_per_map: usize,
+        pub max_value: u64,
+    }
+
+    impl Default for Options {
+        fn default() -> Self {
+            Self {
+                seeds: 20,
+                ranges_per_map: 30,
+                max_value: 1 << 32,
+            }
+        }
+    }
+
+    /// Each map cuts `0..max_value` into blocks and shuffles some of them, so
+    /// neither the source nor the destination ranges overlap.
+    pub fn generate(rng: &mut Rng, options: &Options) -> String {
+        let max_value = options.max_value.max(2);
+        let mut output = String::from("seeds:");
+        for _ in 0..options.seeds {
+            write!(output, " {}", rng.below(max_value)).expect("writing to string");
+        }
+        output.push('\n');
+        for pair in CATEGORIES.windows(2) {
+            write!(output, "\n{}-to-{} map:\n", pair[0], pair[1]).expect("writing to string");
+            let mut cuts = (0..options.ranges_per_map.max(1))
+                .map(|_| rng.below(max_value))
+                .chain([0, max_value])
+                .collect::<Vec<_>>();
+            cuts.sort();
+            cuts.dedup();
+            let blocks = cuts
+                .windows(2)
+                .map(|cut| (cut[0], cut[1] - cut[0]))
+                .collect::<Vec<_>>();
+            let mut order = blocks.clone();
+            rng.shuffle(&mut order);
+            let mut dst_start = 0;
+            let mut lines = Vec::new();
+            for (src_start, range_len) in order {
+                // blocks that keep their position are left to the identity mapping
+                if src_start != dst_start || rng.chance(0.2) {
+                    lines.push(format!("{dst_start} {src_start} {range_len}"));
+                }
+                dst_start += range_len;
+            }
+            rng.shuffle(&mut lines);
+            for line in lines {
+                output.push_str(&line);
+                output.push('\n');
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_input() {
+        let options = day3::Options::default();
+        assert_eq!(
+            day3::generate(&mut Rng::new(7), &options),
+            day3::generate(&mut Rng::new(7), &options)
+        );
+        assert_ne!(
+            day3::generate(&mut Rng::new(7), &options),
+            day3::generate(&mut Rng::new(8), &options)
+        );
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..5 {
+            let mut rng = Rng::new(seed);
+            let input = day1::generate(&mut rng, &day1::Options::default());
+            assert!(crate::day1::part1::solve(&input).is_ok());
+            assert!(crate::day1::part2::solve(&input).is_ok());
+
+            let input = day2::generate(&mut rng, &day2::Options::default());
+            assert!(crate::day2::part1::solve(12, 13, 14, &input).is_ok());
+            assert!(crate::day2::part2::solve(&input).is_ok());
+
+            let input = day3::generate(&mut rng, &day3::Options::default());
+            assert!(crate::day3::part1::solve(&input).is_ok());
+            assert!(crate::day3::part2::solve(&input).is_ok());
+
+            let input = day5::generate(&mut rng, &day5::Options::default());
+            assert!(crate::day5::part1::solve(&input).is_ok());
+        }
+    }
+
+    #[test]
+    fn day4_match_distribution() {
+        let options = day4::Options {
+            cards: 50,
+            match_weights: vec![0, 0, 0, 1],
+            ..Default::default()
+        };
+        let input = day4::generate(&mut Rng::new(1), &options);
+        assert_eq!(input.lines().count(), 50);
+        // three matches on every card
+        assert_eq!(
+            crate::day4::scoring::solve(&input, &crate::day4::scoring::Linear).unwrap(),
+            150
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Character grids addressed by `Node(row, column)`, as in day 3's schematic.
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Node(pub i64, pub i64);
+
+impl Node {
+    pub fn step(self, direction: Direction) -> Self {
+        match direction {
+            Direction::North => Node(self.0 - 1, self.1),
+            Direction::East => Node(self.0, self.1 + 1),
+            Direction::South => Node(self.0 + 1, self.1),
+            Direction::West => Node(self.0, self.1 - 1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
+/// A rectangle of ASCII cells.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid {
+    cells: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+impl Grid {
+    pub fn parse(input: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        for (i, line) in input.lines().enumerate() {
+            if !line.is_ascii() {
+                return Err(format!("row {i} is not ASCII").into());
+            }
+            match width {
+                None => width = Some(line.len()),
+                Some(width) if width != line.len() => {
+                    return Err(
+                        format!("row {i} has {} columns, expected {width}", line.len()).into(),
+                    )
+                }
+                _ => {}
+            }
+            cells.extend(line.bytes());
+        }
+        let width = width.unwrap_or_default();
+        Ok(Self {
+            height: cells.len().checked_div(width).unwrap_or_default(),
+            cells,
+            width,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, node: Node) -> Option<usize> {
+        let (row, column) = (usize::try_from(node.0).ok()?, usize::try_from(node.1).ok()?);
+        (row < self.height && column < self.width).then(|| row * self.width + column)
+    }
+
+    /// `None` outside the grid.
+    pub fn get(&self, node: Node) -> Option<u8> {
+        self.index(node).map(|index| self.cells[index])
+    }
+
+    /// Panics outside the grid.
+    pub fn set(&mut self, node: Node, cell: u8) {
+        let index = self.index(node).expect("node is inside the grid");
+        self.cells[index] = cell;
+    }
+
+    /// Every node, row by row.
+    pub fn nodes(&self) -> impl Iterator<Item = Node> + '_ {
+        (0..self.height as i64).flat_map(|i| (0..self.width as i64).map(move |j| Node(i, j)))
+    }
+
+    pub fn find(&self, cell: u8) -> Option<Node> {
+        self.nodes().find(|node| self.get(*node) == Some(cell))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[u8]> {
+        self.cells.chunks(self.width.max(1))
+    }
+}
+
+impl std::fmt::Display for Grid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{}", String::from_utf8_lossy(row))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let grid = Grid::parse("#.\n.S\n..").unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid.find(b'S'), Some(Node(1, 1)));
+        assert_eq!(grid.get(Node(0, 0)), Some(b'#'));
+        assert_eq!(grid.get(Node(-1, 0)), None);
+        assert_eq!(grid.get(Node(0, 2)), None);
+        assert_eq!(grid.to_string(), "#.\n.S\n..\n");
+        assert_eq!(
+            Grid::parse("#.\n.S.").unwrap_err().to_string(),
+            "row 1 has 3 columns, expected 2"
+        );
+    }
+}
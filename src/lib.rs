@@ -1,5 +1,25 @@
+pub mod batch;
 pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
 pub mod day2;
 pub mod day3;
 pub mod day4;
 pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod examples;
+pub mod gen;
+pub mod grid;
+pub mod num;
+pub mod observe;
+mod par;
+pub mod repl;
+pub mod solvers;
+pub mod watch;
@@ -1,68 +1,319 @@
 use advent_of_code_2023::*;
-use clap::Parser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::io::IsTerminal;
 
+/// Advent of Code 2023 solutions
+///
+/// Puzzle inputs and examples are read at run time from `data/` in the source
+/// checkout the binary was built from, so that checkout has to stay at its
+/// build path. Use `--input` to solve a file from anywhere else.
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[arg(required = true)]
+    day: Option<u8>,
+    #[arg(required = true)]
+    part: Option<u8>,
+    /// Solve this input file instead of the day's puzzle input in the source checkout
+    #[arg(long, conflicts_with = "example")]
+    input: Option<std::path::PathBuf>,
+    /// Solve the day's examples and check them against their expected answers
+    #[arg(long)]
+    example: bool,
+    /// Re-run whenever the input or example files change
+    #[arg(long)]
+    watch: bool,
+    /// Print the annotated day 3 schematic or the day 10 loop instead of solving
+    #[arg(long, value_enum)]
+    render: Option<Render>,
+    /// Score day 4 with this rule instead of the part's own
+    #[arg(long, value_enum)]
+    scoring: Option<Scoring>,
+    /// Grow each empty day 11 row and column to this many instead of the part's own
+    #[arg(long)]
+    expansion: Option<u64>,
+    /// Find day 13 mirrors with this many smudges instead of the part's own
+    #[arg(long)]
+    smudges: Option<u32>,
+    /// Print the day 14 platform after this many spin cycles instead of solving
+    #[arg(long)]
+    cycles: Option<u64>,
+    /// Answer in arbitrary precision instead of failing when a total overflows
+    #[cfg(feature = "bignum")]
+    #[arg(long)]
+    big: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print a random puzzle input in the day's format
+    Gen(GenArgs),
+    /// Solve many inputs for one day and part and tabulate the answers
+    Batch {
+        day: u8,
+        part: u8,
+        /// Input files, directories of inputs, or file-name globs such as `inputs/4-*.txt`
+        #[arg(required = true)]
+        inputs: Vec<std::path::PathBuf>,
+    },
+    /// Parse an input once and answer queries about it, one per line
+    Repl {
+        day: u8,
+        /// Explore this input file instead of the day's puzzle input
+        #[arg(long)]
+        input: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Args)]
+struct GenArgs {
     day: u8,
-    part: u8,
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// Lines, games, cards or seeds to generate (rows and columns for day 3)
+    #[arg(long)]
+    size: Option<usize>,
+    /// Day 1: chance that a digit is spelled out
+    #[arg(long)]
+    spelled: Option<f64>,
+    /// Day 3: chance that a free cell starts a number
+    #[arg(long)]
+    parts: Option<f64>,
+    /// Day 3: chance that a cell between exactly two numbers is a gear
+    #[arg(long)]
+    gears: Option<f64>,
+    /// Day 4: relative weights of 0, 1, 2, ... matches per card
+    #[arg(long, value_delimiter = ',')]
+    matches: Option<Vec<u64>>,
+    /// Day 5: source and destination ranges per map
+    #[arg(long)]
+    ranges: Option<usize>,
+}
+
+fn generate(args: GenArgs) -> Result<String, Box<dyn std::error::Error>> {
+    let mut rng = gen::Rng::new(args.seed);
+    Ok(match args.day {
+        1 => {
+            let mut options = gen::day1::Options::default();
+            options.lines = args.size.unwrap_or(options.lines);
+            options.spelled = args.spelled.unwrap_or(options.spelled);
+            gen::day1::generate(&mut rng, &options)
+        }
+        2 => {
+            let mut options = gen::day2::Options::default();
+            options.games = args.size.unwrap_or(options.games);
+            gen::day2::generate(&mut rng, &options)
+        }
+        3 => {
+            let mut options = gen::day3::Options::default();
+            options.width = args.size.unwrap_or(options.width);
+            options.height = args.size.unwrap_or(options.height);
+            options.part_density = args.parts.unwrap_or(options.part_density);
+            options.gear_density = args.gears.unwrap_or(options.gear_density);
+            gen::day3::generate(&mut rng, &options)
+        }
+        4 => {
+            let mut options = gen::day4::Options::default();
+            options.cards = args.size.unwrap_or(options.cards);
+            options.match_weights = args.matches.unwrap_or(options.match_weights);
+            gen::day4::generate(&mut rng, &options)
+        }
+        5 => {
+            let mut options = gen::day5::Options::default();
+            options.seeds = args.size.unwrap_or(options.seeds);
+            options.ranges_per_map = args.ranges.unwrap_or(options.ranges_per_map);
+            gen::day5::generate(&mut rng, &options)
+        }
+        day => return Err(format!("no generator for day {day}").into()),
+    })
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Render {
+    Ansi,
+    Html,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Scoring {
+    Doubling,
+    Linear,
+    Fibonacci,
+    Cascade,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Cli::parse();
-    match args.day {
-        1 => match args.part {
-            1 => {
-                const DAY_1_PART_1_DATA: &'static str = include_str!("../data/1-1.txt");
-                println!("{}", day1::part1::solve(DAY_1_PART_1_DATA)?);
-            }
-            2 => {
-                const DAY_1_PART_2_DATA: &'static str = include_str!("../data/1-2.txt");
-                println!("{}", day1::part2::solve(DAY_1_PART_2_DATA)?);
-            }
-            _ => unimplemented!(),
-        },
-        2 => match args.part {
-            1 => {
-                const DAY_2_PART_1_DATA: &'static str = include_str!("../data/2-1.txt");
-                // 12 red cubes, 13 green cubes, and 14 blue cubes
-                println!("{}", day2::part1::solve(12, 13, 14, DAY_2_PART_1_DATA,)?);
-            }
-            2 => {
-                const DAY_2_PART_1_DATA: &'static str = include_str!("../data/2-1.txt");
-                println!("{}", day2::part2::solve(DAY_2_PART_1_DATA,)?);
-            }
-            _ => unimplemented!(),
-        },
-        3 => match args.part {
-            1 => {
-                const DAY_3_PART_1_DATA: &'static str = include_str!("../data/3-1.txt");
-                println!("{}", day3::part1::solve(DAY_3_PART_1_DATA,));
-            }
-            2 => {
-                const DAY_3_PART_1_DATA: &'static str = include_str!("../data/3-1.txt");
-                println!("{}", day3::part2::solve(DAY_3_PART_1_DATA,));
-            }
-            _ => unimplemented!(),
-        },
-        4 => match args.part {
-            1 => {
-                const DAY_4_PART_1_DATA: &'static str = include_str!("../data/4-1.txt");
-                println!("{}", day4::part1::solve(DAY_4_PART_1_DATA)?);
-            }
-            2 => {
-                const DAY_4_PART_2_DATA: &'static str = include_str!("../data/4-1.txt");
-                println!("{}", day4::part2::solve(DAY_4_PART_2_DATA)?);
+    match args.command {
+        Some(Command::Gen(gen_args)) => {
+            print!("{}", generate(gen_args)?);
+            return Ok(());
+        }
+        Some(Command::Batch { day, part, inputs }) => {
+            let solver = solvers::get(day, part)
+                .ok_or_else(|| format!("day {day} part {part} is not solved yet"))?;
+            let runs = batch::solve_all(solver, &batch::expand(&inputs)?);
+            print!("{}", batch::table(&runs));
+            return Ok(());
+        }
+        Some(Command::Repl { day, input }) => {
+            let path = input.unwrap_or_else(|| solvers::input_path(day, 1));
+            let input = std::fs::read_to_string(&path)
+                .map_err(|e| format!("could not read {}: {e}", path.display()))?;
+            let model = repl::load(day, &input)?;
+            repl::run(model.as_ref(), std::io::stdin().lock(), std::io::stdout())?;
+            return Ok(());
+        }
+        None => {}
+    }
+    let (Some(day), Some(part)) = (args.day, args.part) else {
+        unreachable!("clap requires day and part without a subcommand");
+    };
+    let path = args
+        .input
+        .clone()
+        .unwrap_or_else(|| solvers::input_path(day, part));
+    if args.watch {
+        return watch(&args, day, part, path);
+    }
+    if args.example {
+        let (output, failures) = run_examples(day, part)?;
+        print!("{output}");
+        if failures > 0 {
+            return Err(format!("{failures} example(s) did not match").into());
+        }
+    } else {
+        print!("{}", solve(&args, day, part, &path)?);
+    }
+    Ok(())
+}
+
+fn solve(
+    args: &Cli,
+    day: u8,
+    part: u8,
+    path: &std::path::Path,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let input = std::fs::read_to_string(path)
+        .map_err(|e| format!("could not read {}: {e}", path.display()))?;
+    Ok(match day {
+        3 if args.render.is_some() => {
+            let format = match args.render {
+                Some(Render::Html) => day3::render::Format::Html,
+                _ => day3::render::Format::Ansi,
+            };
+            day3::render::render(&input, Default::default(), format)?
+        }
+        4 if args.scoring.is_some() => {
+            let rule: &dyn day4::scoring::ScoringRule = match args.scoring {
+                Some(Scoring::Doubling) => &day4::scoring::Doubling,
+                Some(Scoring::Linear) => &day4::scoring::Linear,
+                Some(Scoring::Fibonacci) => &day4::scoring::Fibonacci,
+                _ => &day4::scoring::CopyCascade::default(),
+            };
+            format!("{}\n", day4::scoring::solve(&input, rule)?)
+        }
+        10 if args.render.is_some() => {
+            let format = match args.render {
+                Some(Render::Html) => day10::render::Format::Html,
+                _ if std::io::stdout().is_terminal() => day10::render::Format::Ansi,
+                _ => day10::render::Format::Plain,
+            };
+            day10::render::render(&input, format)?
+        }
+        11 if args.expansion.is_some() => {
+            let factor = args.expansion.expect("checked by the guard");
+            format!("{}\n", day11::solve_with(&input, factor)?)
+        }
+        13 if args.smudges.is_some() => {
+            let smudges = args.smudges.expect("checked by the guard");
+            format!("{}\n", day13::solve_with(&input, smudges)?)
+        }
+        14 if args.cycles.is_some() => {
+            let cycles = args.cycles.expect("checked by the guard");
+            day14::spin(&input, cycles)?.to_string()
+        }
+        #[cfg(feature = "bignum")]
+        _ if args.big => {
+            let solver = solvers::get_big(day, part)
+                .ok_or_else(|| format!("day {day} part {part} has no big-number solver"))?;
+            format!("{}\n", solver(&input)?)
+        }
+        _ => {
+            let solver = solvers::get(day, part)
+                .ok_or_else(|| format!("day {day} part {part} is not solved yet"))?;
+            format!("{}\n", solver(&input)?)
+        }
+    })
+}
+
+/// Solves every example, returning the report and how many did not match.
+fn run_examples(day: u8, part: u8) -> Result<(String, usize), Box<dyn std::error::Error>> {
+    let solver = solvers::get(day, part)
+        .ok_or_else(|| format!("day {day} part {part} is not solved yet"))?;
+    let mut output = String::new();
+    let mut failures = 0;
+    for example in examples::load(day)? {
+        let answer = solver(&example.input).map(|answer| answer.to_string());
+        let line = match (answer, example.expected(part)) {
+            (Ok(answer), Some(expected)) if answer == expected => format!("{answer} ok"),
+            (Ok(answer), Some(expected)) => {
+                failures += 1;
+                format!("{answer} expected {expected}")
             }
-            _ => unimplemented!(),
-        },
-        5 => match args.part {
-            1 => {
-                const DAY_5_PART_1_DATA: &'static str = include_str!("../data/5-1.txt");
-                println!("{}", day5::part1::solve(DAY_5_PART_1_DATA)?);
+            (Ok(answer), None) => answer,
+            (Err(e), Some(_)) => {
+                failures += 1;
+                format!("error: {e}")
             }
-            _ => unimplemented!(),
-        },
-        _ => unimplemented!(),
+            (Err(e), None) => format!("error: {e}"),
+        };
+        output.push_str(&format!("{}: {line}\n", example.name));
+    }
+    Ok((output, failures))
+}
+
+fn watch(
+    args: &Cli,
+    day: u8,
+    part: u8,
+    path: std::path::PathBuf,
+) -> Result<(), Box<dyn std::error::Error>> {
+    const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+    // The examples are listed again after every change, so one added while
+    // watching is picked up and its later edits are seen too.
+    let watched = |path: &std::path::Path| -> Result<_, Box<dyn std::error::Error>> {
+        let mut watched = vec![path.to_path_buf(), examples::dir()];
+        watched.extend(examples::load(day)?.into_iter().flat_map(|example| {
+            let expected = example.path.with_extension("expected");
+            [example.path, expected]
+        }));
+        Ok(watched)
+    };
+    let mut watcher = watch::Watcher::new(watched(&path)?);
+    let mut previous: Option<String> = None;
+    loop {
+        let start = std::time::Instant::now();
+        let output = if args.example {
+            run_examples(day, part).map(|(output, _)| output)
+        } else {
+            solve(args, day, part, &path)
+        }
+        .unwrap_or_else(|e| format!("error: {e}\n"));
+        let elapsed = start.elapsed();
+        match &previous {
+            None => print!("{output}"),
+            Some(previous) => println!("{}", watch::diff(previous, &output)),
+        }
+        println!("-- solved in {elapsed:.1?}, watching for changes");
+        previous = Some(output);
+        while !watcher.changed() {
+            std::thread::sleep(POLL_INTERVAL);
+        }
+        if let Ok(paths) = watched(&path) {
+            watcher.set_paths(paths);
+        }
     }
-    Ok(())
 }
@@ -0,0 +1,80 @@
+//! Totals that the solvers accumulate into. Every operation is checked, so a
+//! fixed-width total reports overflow instead of wrapping, and with the
+//! `bignum` feature the same solvers can answer in arbitrary precision.
+
+use std::fmt::{Debug, Display};
+
+pub trait Number: Sized + Clone + Debug + Display + PartialEq + Send {
+    /// `None` when `n` does not fit in the type.
+    fn from_u64(n: u64) -> Option<Self>;
+    /// `None` when `n` does not fit in the type, e.g. negative for unsigned.
+    fn from_i64(n: i64) -> Option<Self>;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+
+    fn zero() -> Self {
+        Self::from_u64(0).expect("every number type holds 0")
+    }
+
+    fn one() -> Self {
+        Self::from_u64(1).expect("every number type holds 1")
+    }
+}
+
+impl Number for u64 {
+    fn from_u64(n: u64) -> Option<Self> {
+        Some(n)
+    }
+
+    fn from_i64(n: i64) -> Option<Self> {
+        n.try_into().ok()
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        u64::checked_add(*self, *other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        u64::checked_mul(*self, *other)
+    }
+}
+
+impl Number for i64 {
+    fn from_u64(n: u64) -> Option<Self> {
+        n.try_into().ok()
+    }
+
+    fn from_i64(n: i64) -> Option<Self> {
+        Some(n)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        i64::checked_add(*self, *other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        i64::checked_mul(*self, *other)
+    }
+}
+
+#[cfg(feature = "bignum")]
+pub type Big = num_bigint::BigInt;
+
+#[cfg(feature = "bignum")]
+impl Number for Big {
+    fn from_u64(n: u64) -> Option<Self> {
+        Some(n.into())
+    }
+
+    fn from_i64(n: i64) -> Option<Self> {
+        Some(n.into())
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+}
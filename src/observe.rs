@@ -0,0 +1,259 @@
+//! Hooks for watching a solver work without changing it. Solvers report
+//! [`Event`]s to an [`Observer`]; the plain `solve` functions pass `&()`, whose
+//! empty `event` is inlined away so unobserved runs cost nothing.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// Day 1: a digit taken as the first or last digit of a line, found at
+    /// `column` either as a numeral or spelled out.
+    Digit {
+        line: usize,
+        column: usize,
+        digit: u64,
+        spelled: bool,
+    },
+    /// Day 2: a round showing more cubes of some colour than the bag holds.
+    RoundRejected {
+        game: u64,
+        round: usize,
+        red: u64,
+        green: u64,
+        blue: u64,
+    },
+    /// Day 3: a `*` next to exactly two parts.
+    GearAccepted {
+        row: i64,
+        column: i64,
+        parts: [i64; 2],
+    },
+    /// Day 4: every copy of `card` wins a copy of `prize`.
+    CascadeStep { card: u64, prize: u64 },
+    /// Day 5: a seed's value mapped from one category to the next.
+    MapHop {
+        seed: u64,
+        from: &'static str,
+        to: &'static str,
+        value: u64,
+        mapped: u64,
+    },
+}
+
+impl Event {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Event::Digit { .. } => "digit",
+            Event::RoundRejected { .. } => "round rejected",
+            Event::GearAccepted { .. } => "gear accepted",
+            Event::CascadeStep { .. } => "cascade step",
+            Event::MapHop { .. } => "map hop",
+        }
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Digit {
+                line,
+                column,
+                digit,
+                spelled,
+            } => {
+                let form = if *spelled { "spelled" } else { "numeral" };
+                write!(f, "line {line}, column {column}: digit {digit} ({form})")
+            }
+            Event::RoundRejected {
+                game,
+                round,
+                red,
+                green,
+                blue,
+            } => write!(
+                f,
+                "game {game}, round {round}: rejected {red} red, {green} green, {blue} blue"
+            ),
+            Event::GearAccepted { row, column, parts } => write!(
+                f,
+                "gear at row {row}, column {column}: {} * {}",
+                parts[0], parts[1]
+            ),
+            Event::CascadeStep { card, prize } => write!(f, "card {card} wins card {prize}"),
+            Event::MapHop {
+                seed,
+                from,
+                to,
+                value,
+                mapped,
+            } => write!(f, "seed {seed}: {from} {value} -> {to} {mapped}"),
+        }
+    }
+}
+
+/// Receives events as they happen. Lines, cards and seeds may be handled on
+/// several threads under the `parallel` feature, so observers take `&self`.
+pub trait Observer: Sync {
+    fn event(&self, event: Event);
+}
+
+impl Observer for () {
+    #[inline(always)]
+    fn event(&self, _: Event) {}
+}
+
+/// Writes each event on its own line.
+#[derive(Debug)]
+pub struct Log<W>(Mutex<W>);
+
+impl<W: std::io::Write + Send> Log<W> {
+    pub fn new(writer: W) -> Self {
+        Self(Mutex::new(writer))
+    }
+
+    pub fn into_inner(self) -> W {
+        self.0.into_inner().expect("no writer panics")
+    }
+}
+
+impl<W: std::io::Write + Send> Observer for Log<W> {
+    fn event(&self, event: Event) {
+        // a failed write only loses the log line, not the answer
+        let _ = writeln!(self.0.lock().expect("no writer panics"), "{event}");
+    }
+}
+
+/// Counts events by kind.
+#[derive(Debug, Default)]
+pub struct Counter(Mutex<HashMap<&'static str, usize>>);
+
+impl Counter {
+    pub fn count(&self, kind: &str) -> usize {
+        let counts = self.0.lock().expect("no counter panics");
+        counts.get(kind).copied().unwrap_or_default()
+    }
+}
+
+impl Observer for Counter {
+    fn event(&self, event: Event) {
+        *self
+            .0
+            .lock()
+            .expect("no counter panics")
+            .entry(event.kind())
+            .or_default() += 1;
+    }
+}
+
+/// Keeps every event. Events from parallel work arrive in no fixed order.
+#[derive(Debug, Default)]
+pub struct Trace(Mutex<Vec<Event>>);
+
+impl Trace {
+    pub fn events(self) -> Vec<Event> {
+        self.0.into_inner().expect("no trace panics")
+    }
+}
+
+impl Observer for Trace {
+    fn event(&self, event: Event) {
+        self.0.lock().expect("no trace panics").push(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day1_digits() {
+        let trace = Trace::default();
+        crate::day1::part2::solve_observed("two1nine\ntreb7uchet", &trace).unwrap();
+        assert_eq!(
+            trace.events(),
+            vec![
+                Event::Digit {
+                    line: 0,
+                    column: 0,
+                    digit: 2,
+                    spelled: true,
+                },
+                Event::Digit {
+                    line: 0,
+                    column: 4,
+                    digit: 9,
+                    spelled: true,
+                },
+                Event::Digit {
+                    line: 1,
+                    column: 4,
+                    digit: 7,
+                    spelled: false,
+                },
+                Event::Digit {
+                    line: 1,
+                    column: 4,
+                    digit: 7,
+                    spelled: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn day2_rejected_rounds() {
+        let input = include_str!("../data/examples/2-1.txt");
+        let log = Log::new(Vec::new());
+        crate::day2::part1::solve_observed(12, 13, 14, input, &log).unwrap();
+        let mut lines = String::from_utf8(log.into_inner())
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        lines.sort();
+        assert_eq!(
+            lines,
+            vec![
+                "game 3, round 0: rejected 20 red, 8 green, 6 blue",
+                "game 4, round 2: rejected 14 red, 3 green, 15 blue",
+            ]
+        );
+    }
+
+    #[test]
+    fn day3_gears() {
+        let input = include_str!("../data/examples/3-1.txt");
+        let trace = Trace::default();
+        crate::day3::part2::solve_observed::<u64, _>(input, Default::default(), &trace).unwrap();
+        assert_eq!(
+            trace.events(),
+            vec![
+                Event::GearAccepted {
+                    row: 1,
+                    column: 3,
+                    parts: [467, 35],
+                },
+                Event::GearAccepted {
+                    row: 8,
+                    column: 5,
+                    parts: [755, 598],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn counts() {
+        let counter = Counter::default();
+        let input = include_str!("../data/examples/4-1.txt");
+        crate::day4::part2::solve_observed(input, &counter).unwrap();
+        // matches per card: 4, 2, 2, 1, 0, 0
+        assert_eq!(counter.count("cascade step"), 9);
+        let input = include_str!("../data/examples/5-1.txt");
+        crate::day5::part1::solve_observed(input, &counter).unwrap();
+        assert_eq!(counter.count("map hop"), 4 * 7);
+        assert_eq!(counter.count("digit"), 0);
+    }
+}
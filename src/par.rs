@@ -0,0 +1,72 @@
+//! Maps fallible work over independent items, on rayon's thread pool when the
+//! `parallel` feature is enabled. Results come back in input order and the
+//! first error stops the remaining work. When several items fail in parallel,
+//! the error returned is one of them but not necessarily the earliest.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Applies `f` to each line with its index.
+#[cfg(feature = "parallel")]
+pub(crate) fn map_lines<'a, T, E, F>(input: &'a str, f: F) -> Result<Vec<T>, E>
+where
+    T: Send,
+    E: Send,
+    F: Fn(usize, &'a str) -> Result<T, E> + Send + Sync,
+{
+    let lines = input.lines().collect::<Vec<_>>();
+    lines
+        .into_par_iter()
+        .enumerate()
+        .map(|(i, line)| f(i, line))
+        .collect()
+}
+
+/// Applies `f` to each line with its index.
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn map_lines<'a, T, E, F>(input: &'a str, f: F) -> Result<Vec<T>, E>
+where
+    T: Send,
+    E: Send,
+    F: Fn(usize, &'a str) -> Result<T, E> + Send + Sync,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| f(i, line))
+        .collect()
+}
+
+#[cfg(feature = "parallel")]
+pub(crate) fn map_items<'a, S, T, E, F>(items: &'a [S], f: F) -> Result<Vec<T>, E>
+where
+    S: Sync,
+    T: Send,
+    E: Send,
+    F: Fn(&'a S) -> Result<T, E> + Send + Sync,
+{
+    items.par_iter().map(f).collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn map_items<'a, S, T, E, F>(items: &'a [S], f: F) -> Result<Vec<T>, E>
+where
+    S: Sync,
+    T: Send,
+    E: Send,
+    F: Fn(&'a S) -> Result<T, E> + Send + Sync,
+{
+    items.iter().map(f).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_lines_in_order() {
+        let parse = |i, line: &str| line.parse::<u64>().map_err(|_| i);
+        assert_eq!(map_lines("3\n1\n2", parse), Ok(vec![3, 1, 2]));
+        assert_eq!(map_lines("3\nx\n2", parse), Err(1));
+    }
+}
@@ -0,0 +1,119 @@
+//! Line-by-line queries against an input that is parsed once up front.
+
+use std::io::{BufRead, Write};
+
+/// A day's parsed input, answering commands split into words.
+pub trait Model {
+    /// One line per command the model understands.
+    fn help(&self) -> &'static str;
+    fn query(&self, words: &[&str]) -> Result<String, Box<dyn std::error::Error>>;
+}
+
+pub fn load(day: u8, input: &str) -> Result<Box<dyn Model>, Box<dyn std::error::Error>> {
+    Ok(match day {
+        2 => Box::new(crate::day2::repl::Games::parse(input)?),
+        3 => Box::new(crate::day3::repl::Schematic::parse(input)?),
+        4 => Box::new(crate::day4::repl::Cards::parse(input)?),
+        5 => Box::new(crate::day5::repl::Almanac::parse(input)?),
+        day => return Err(format!("no model for day {day}").into()),
+    })
+}
+
+/// Reads commands until `quit` or end of input. A failed command prints its
+/// error and leaves the session running.
+pub fn run<R, W>(model: &dyn Model, input: R, mut output: W) -> std::io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+{
+    write!(output, "> ")?;
+    output.flush()?;
+    for line in input.lines() {
+        let line = line?;
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        match words[..] {
+            [] => {}
+            ["quit" | "exit"] => return Ok(()),
+            ["help"] => writeln!(output, "{}\nhelp\nquit", model.help())?,
+            _ => match model.query(&words) {
+                Ok(answer) => writeln!(output, "{answer}")?,
+                Err(e) => writeln!(output, "error: {e}")?,
+            },
+        }
+        write!(output, "> ")?;
+        output.flush()?;
+    }
+    Ok(())
+}
+
+/// Parses a command argument, naming it in the error.
+pub(crate) fn arg<T>(word: &str, name: &str) -> Result<T, String>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    word.parse()
+        .map_err(|e| format!("invalid {name} {word}: {e}"))
+}
+
+pub(crate) fn unknown(words: &[&str], help: &str) -> Box<dyn std::error::Error> {
+    match help
+        .lines()
+        .find(|usage| usage.split(' ').next() == words.first().copied())
+    {
+        Some(usage) => format!("usage: {usage}").into(),
+        None => format!("unknown command {}, try help", words.join(" ")).into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(day: u8, input: &str, commands: &str) -> String {
+        let model = load(day, input).unwrap();
+        let mut output = Vec::new();
+        run(model.as_ref(), commands.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn queries() {
+        let input = include_str!("../data/examples/5-1.txt");
+        assert_eq!(
+            session(5, input, "map seed 79 location\nmap seed 14 soil\n"),
+            "> 82\n> 14\n> "
+        );
+        let input = include_str!("../data/examples/4-1.txt");
+        assert_eq!(
+            session(4, input, "card 3 matches\ncard 1 points\ncard 5 copies\n"),
+            "> 2\n> 8\n> 14\n> "
+        );
+        let input = include_str!("../data/examples/2-1.txt");
+        assert_eq!(
+            session(2, input, "game 4 min\n"),
+            "> 14 red, 3 green, 15 blue (power 630)\n> "
+        );
+        let input = include_str!("../data/examples/3-1.txt");
+        assert_eq!(
+            session(3, input, "neighbours 1 3\nneighbours 2 5\n"),
+            "> parts: 467, 35\nsymbols: none\n> parts: 633\nsymbols: #\n> "
+        );
+    }
+
+    #[test]
+    fn errors_keep_going() {
+        let input = include_str!("../data/examples/4-1.txt");
+        assert_eq!(
+            session(
+                4,
+                input,
+                "card 9 matches\ncard x\ncard x matches\nfrobnicate\n\nquit\ncard 1 matches\n"
+            ),
+            "> error: no card 9\n> error: usage: card <id> matches|points|copies\n\
+             > error: invalid card id x: invalid digit found in string\n\
+             > error: unknown command frobnicate, try help\n> > "
+        );
+        assert!(load(1, "").is_err());
+    }
+}
@@ -0,0 +1,161 @@
+use std::fmt;
+use std::path::PathBuf;
+
+/// A puzzle answer. Most are counts, but some days work with negative numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::Unsigned(n)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Signed(n)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(n) => write!(f, "{n}"),
+            Answer::Signed(n) => write!(f, "{n}"),
+        }
+    }
+}
+
+pub type Solver = fn(&str) -> Result<Answer, Box<dyn std::error::Error>>;
+
+/// Every solved day and part, in order.
+pub const SOLVERS: &[(u8, u8, Solver)] = &[
+    (1, 1, |input| Ok(crate::day1::part1::solve(input)?.into())),
+    (1, 2, |input| Ok(crate::day1::part2::solve(input)?.into())),
+    // 12 red cubes, 13 green cubes, and 14 blue cubes
+    (2, 1, |input| {
+        Ok(crate::day2::part1::solve(12, 13, 14, input)?.into())
+    }),
+    (2, 2, |input| Ok(crate::day2::part2::solve(input)?.into())),
+    (3, 1, |input| Ok(crate::day3::part1::solve(input)?.into())),
+    (3, 2, |input| Ok(crate::day3::part2::solve(input)?.into())),
+    (4, 1, |input| Ok(crate::day4::part1::solve(input)?.into())),
+    (4, 2, |input| Ok(crate::day4::part2::solve(input)?.into())),
+    (5, 1, |input| Ok(crate::day5::part1::solve(input)?.into())),
+    (6, 1, |input| Ok(crate::day6::part1::solve(input)?.into())),
+    (6, 2, |input| Ok(crate::day6::part2::solve(input)?.into())),
+    (7, 1, |input| Ok(crate::day7::part1::solve(input)?.into())),
+    (7, 2, |input| Ok(crate::day7::part2::solve(input)?.into())),
+    (8, 1, |input| Ok(crate::day8::part1::solve(input)?.into())),
+    (8, 2, |input| Ok(crate::day8::part2::solve(input)?.into())),
+    (9, 1, |input| Ok(crate::day9::part1::solve(input)?.into())),
+    (9, 2, |input| Ok(crate::day9::part2::solve(input)?.into())),
+    (10, 1, |input| Ok(crate::day10::part1::solve(input)?.into())),
+    (10, 2, |input| Ok(crate::day10::part2::solve(input)?.into())),
+    (11, 1, |input| Ok(crate::day11::part1::solve(input)?.into())),
+    (11, 2, |input| Ok(crate::day11::part2::solve(input)?.into())),
+    (12, 1, |input| Ok(crate::day12::part1::solve(input)?.into())),
+    (12, 2, |input| Ok(crate::day12::part2::solve(input)?.into())),
+    (13, 1, |input| Ok(crate::day13::part1::solve(input)?.into())),
+    (13, 2, |input| Ok(crate::day13::part2::solve(input)?.into())),
+    (14, 1, |input| Ok(crate::day14::part1::solve(input)?.into())),
+    (14, 2, |input| Ok(crate::day14::part2::solve(input)?.into())),
+    (15, 1, |input| Ok(crate::day15::part1::solve(input)?.into())),
+    (15, 2, |input| Ok(crate::day15::part2::solve(input)?.into())),
+];
+
+pub fn get(day: u8, part: u8) -> Option<Solver> {
+    SOLVERS
+        .iter()
+        .find(|(d, p, _)| *d == day && *p == part)
+        .map(|(_, _, solver)| *solver)
+}
+
+#[cfg(feature = "bignum")]
+pub type BigSolver = fn(&str) -> Result<crate::num::Big, Box<dyn std::error::Error>>;
+
+/// The days and parts whose totals can outgrow a `u64`, answering in arbitrary precision.
+#[cfg(feature = "bignum")]
+pub const BIG_SOLVERS: &[(u8, u8, BigSolver)] = &[
+    (1, 1, |input| Ok(crate::day1::part1::solve_as(input)?)),
+    (1, 2, |input| Ok(crate::day1::part2::solve_as(input)?)),
+    (2, 2, crate::day2::part2::solve_as),
+    (3, 1, |input| {
+        crate::day3::part1::solve_with(input, Default::default())
+    }),
+    (3, 2, |input| {
+        crate::day3::part2::solve_with(input, Default::default())
+    }),
+    (4, 1, crate::day4::part1::solve_as),
+    (4, 2, crate::day4::part2::solve_as),
+];
+
+#[cfg(feature = "bignum")]
+pub fn get_big(day: u8, part: u8) -> Option<BigSolver> {
+    BIG_SOLVERS
+        .iter()
+        .find(|(d, p, _)| *d == day && *p == part)
+        .map(|(_, _, solver)| *solver)
+}
+
+/// The puzzle input for a day, preferring a part-specific file when there is one.
+/// The path points into the source checkout the crate was built from.
+pub fn input_path(day: u8, part: u8) -> PathBuf {
+    let data = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data");
+    let part_specific = data.join(format!("{day}-{part}.txt"));
+    if part_specific.exists() {
+        part_specific
+    } else {
+        data.join(format!("{day}-1.txt"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    fn golden_examples() {
+        for (day, part, solver) in SOLVERS {
+            let examples = examples::load(*day).unwrap();
+            let mut checked = 0;
+            for example in examples {
+                if let Some(expected) = example.expected(*part) {
+                    let answer = solver(&example.input)
+                        .map_err(|e| format!("day {day} part {part} on {}: {e}", example.name))
+                        .unwrap();
+                    assert_eq!(
+                        answer.to_string(),
+                        expected,
+                        "day {day} part {part} on {}",
+                        example.name
+                    );
+                    checked += 1;
+                }
+            }
+            assert!(checked > 0, "no examples for day {day} part {part}");
+        }
+    }
+
+    #[cfg(feature = "bignum")]
+    #[test]
+    fn big_solvers_agree() {
+        for (day, part, solver) in BIG_SOLVERS {
+            for example in examples::load(*day).unwrap() {
+                if let Some(expected) = example.expected(*part) {
+                    assert_eq!(solver(&example.input).unwrap().to_string(), expected);
+                }
+            }
+        }
+        let input = "5000000000*5000000000";
+        assert!(get(3, 2).unwrap()(input).is_err());
+        assert_eq!(
+            get_big(3, 2).unwrap()(input).unwrap().to_string(),
+            "25000000000000000000"
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Detects changes to a set of files by polling their modification time and
+/// length, so it works anywhere without an OS notification service.
+#[derive(Debug)]
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    stamps: Vec<Option<(SystemTime, u64)>>,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let stamps = paths.iter().map(|path| stamp(path)).collect();
+        Self { paths, stamps }
+    }
+
+    /// Whether any file was modified, created or removed since the last call.
+    pub fn changed(&mut self) -> bool {
+        let stamps = self
+            .paths
+            .iter()
+            .map(|path| stamp(path))
+            .collect::<Vec<_>>();
+        let changed = stamps != self.stamps;
+        self.stamps = stamps;
+        changed
+    }
+
+    /// Replaces the watched files. Newly added files are stamped now, so only
+    /// their later changes are reported.
+    pub fn set_paths(&mut self, paths: Vec<PathBuf>) {
+        self.stamps = paths.iter().map(|path| stamp(path)).collect();
+        self.paths = paths;
+    }
+}
+
+fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Line by line comparison of two runs' output, listing only what changed.
+pub fn diff(previous: &str, current: &str) -> String {
+    if previous == current {
+        return "unchanged".to_string();
+    }
+    let previous = previous.lines().collect::<Vec<_>>();
+    let current = current.lines().collect::<Vec<_>>();
+    let mut output = Vec::new();
+    for i in 0..previous.len().max(current.len()) {
+        match (previous.get(i), current.get(i)) {
+            (Some(old), Some(new)) if old == new => {}
+            (old, new) => {
+                output.extend(old.map(|old| format!("- {old}")));
+                output.extend(new.map(|new| format!("+ {new}")));
+            }
+        }
+    }
+    output.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_answers() {
+        assert_eq!(diff("142\n", "142\n"), "unchanged");
+        assert_eq!(diff("142\n", "281\n"), "- 142\n+ 281");
+        assert_eq!(
+            diff(
+                "1-1: 142 ok\n1-2: 281 ok\n",
+                "1-1: 142 ok\n1-2: 280 expected 281\n3\n"
+            ),
+            "- 1-2: 281 ok\n+ 1-2: 280 expected 281\n+ 3"
+        );
+    }
+
+    #[test]
+    fn detects_changes() {
+        let path = std::env::temp_dir().join(format!("aoc-watch-{}.txt", std::process::id()));
+        std::fs::write(&path, "1").unwrap();
+        let mut watcher = Watcher::new(vec![path.clone()]);
+        assert!(!watcher.changed());
+        std::fs::write(&path, "12").unwrap();
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+        std::fs::remove_file(&path).unwrap();
+        assert!(watcher.changed());
+    }
+
+    #[test]
+    fn watches_added_paths() {
+        let path = std::env::temp_dir().join(format!("aoc-watch-added-{}.txt", std::process::id()));
+        std::fs::write(&path, "1").unwrap();
+        let mut watcher = Watcher::new(Vec::new());
+        watcher.set_paths(vec![path.clone()]);
+        assert!(!watcher.changed());
+        std::fs::write(&path, "12").unwrap();
+        assert!(watcher.changed());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
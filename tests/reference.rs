@@ -0,0 +1,296 @@
+//! Compares the optimised solvers against naive reference implementations on
+//! randomly generated inputs. Proptest shrinks any disagreement down to a
+//! minimal input before reporting it.
+
+use advent_of_code_2023::*;
+use proptest::prelude::*;
+
+mod reference {
+    pub mod day3 {
+        fn grid(input: &str) -> Vec<Vec<char>> {
+            input.lines().map(|line| line.chars().collect()).collect()
+        }
+
+        /// Every number as (row, first column, last column, value).
+        fn numbers(grid: &[Vec<char>]) -> Vec<(usize, usize, usize, i64)> {
+            let mut numbers = Vec::new();
+            for (i, row) in grid.iter().enumerate() {
+                let mut j = 0;
+                while j < row.len() {
+                    if row[j].is_ascii_digit() {
+                        let start = j;
+                        while j < row.len() && row[j].is_ascii_digit() {
+                            j += 1;
+                        }
+                        let value = row[start..j].iter().collect::<String>().parse().unwrap();
+                        numbers.push((i, start, j - 1, value));
+                    } else {
+                        j += 1;
+                    }
+                }
+            }
+            numbers
+        }
+
+        fn touches(number: &(usize, usize, usize, i64), i: usize, j: usize) -> bool {
+            let (row, start, end, _) = *number;
+            i + 1 >= row && i <= row + 1 && j + 1 >= start && j <= end + 1
+        }
+
+        pub fn part1(input: &str) -> i64 {
+            let grid = grid(input);
+            numbers(&grid)
+                .iter()
+                .filter(|number| {
+                    grid.iter().enumerate().any(|(i, row)| {
+                        row.iter()
+                            .enumerate()
+                            .any(|(j, c)| *c != '.' && !c.is_ascii_digit() && touches(number, i, j))
+                    })
+                })
+                .map(|number| number.3)
+                .sum()
+        }
+
+        pub fn part2(input: &str) -> i64 {
+            let grid = grid(input);
+            let numbers = numbers(&grid);
+            let mut total = 0;
+            for (i, row) in grid.iter().enumerate() {
+                for (j, c) in row.iter().enumerate() {
+                    if *c != '*' {
+                        continue;
+                    }
+                    let touching = numbers
+                        .iter()
+                        .filter(|number| touches(number, i, j))
+                        .collect::<Vec<_>>();
+                    if touching.len() == 2 {
+                        total += touching[0].3 * touching[1].3;
+                    }
+                }
+            }
+            total
+        }
+    }
+
+    pub mod day4 {
+        fn matches(input: &str) -> Vec<usize> {
+            input
+                .lines()
+                .map(|line| {
+                    let (_, numbers) = line.split_once(':').unwrap();
+                    let (winning, player) = numbers.split_once('|').unwrap();
+                    let winning = winning.split_whitespace().collect::<Vec<_>>();
+                    player
+                        .split_whitespace()
+                        .filter(|n| winning.contains(n))
+                        .count()
+                })
+                .collect()
+        }
+
+        pub fn part1(input: &str) -> u64 {
+            matches(input)
+                .into_iter()
+                .map(|n| if n == 0 { 0 } else { 1 << (n - 1) })
+                .sum()
+        }
+
+        pub fn part2(input: &str) -> u64 {
+            let matches = matches(input);
+            let mut copies = vec![1; matches.len()];
+            for i in 0..matches.len() {
+                for j in i + 1..=i + matches[i] {
+                    if j < copies.len() {
+                        copies[j] += copies[i];
+                    }
+                }
+            }
+            copies.iter().sum()
+        }
+    }
+
+    pub mod day5 {
+        pub fn part1(input: &str) -> u64 {
+            let mut sections = input.split("\n\n");
+            let seeds = sections.next().unwrap();
+            let maps = sections
+                .map(|section| {
+                    section
+                        .lines()
+                        .skip(1)
+                        .map(|line| {
+                            let numbers = line
+                                .split_whitespace()
+                                .map(|n| n.parse::<u64>().unwrap())
+                                .collect::<Vec<_>>();
+                            (numbers[0], numbers[1], numbers[2])
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            seeds
+                .split_whitespace()
+                .skip(1)
+                .map(|seed| {
+                    maps.iter()
+                        .fold(seed.parse::<u64>().unwrap(), |value, map| {
+                            map.iter()
+                                .find(|(_, src, len)| value >= *src && value < src + len)
+                                .map(|(dst, src, _)| dst + (value - src))
+                                .unwrap_or(value)
+                        })
+                })
+                .min()
+                .unwrap()
+        }
+    }
+}
+
+fn schematic() -> impl Strategy<Value = String> {
+    let cell = prop_oneof![
+        6 => Just('.'),
+        4 => proptest::char::range('0', '9'),
+        1 => Just('*'),
+        1 => prop::sample::select(vec!['#', '$', '+', '/']),
+    ];
+    (1..9usize, 1..12usize).prop_flat_map(move |(width, height)| {
+        prop::collection::vec(
+            prop::collection::vec(cell.clone(), width).prop_map(String::from_iter),
+            height,
+        )
+        .prop_map(|rows| rows.join("\n"))
+    })
+}
+
+fn scratchcards() -> impl Strategy<Value = String> {
+    let numbers = || {
+        prop::collection::btree_set(1..100u64, 0..12)
+            .prop_map(Vec::from_iter)
+            .prop_shuffle()
+    };
+    prop::collection::vec((numbers(), numbers()), 1..20).prop_map(|cards| {
+        cards
+            .into_iter()
+            .enumerate()
+            .map(|(i, (winning, player))| {
+                let join = |numbers: Vec<u64>| {
+                    numbers
+                        .iter()
+                        .map(|n| format!("{n:>2}"))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                };
+                format!("Card {}: {} | {}", i + 1, join(winning), join(player))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    })
+}
+
+fn almanac() -> impl Strategy<Value = String> {
+    const MAPS: [&str; 7] = [
+        "seed-to-soil",
+        "soil-to-fertilizer",
+        "fertilizer-to-water",
+        "water-to-light",
+        "light-to-temperature",
+        "temperature-to-humidity",
+        "humidity-to-location",
+    ];
+    // consecutive (gap, length) pairs keep the source ranges from overlapping
+    let map = prop::collection::vec((0..50u64, 1..50u64, 0..1000u64), 0..6)
+        .prop_map(|ranges| {
+            let mut src_start = 0;
+            ranges
+                .into_iter()
+                .map(|(gap, range_len, dst_start)| {
+                    src_start += gap;
+                    let line = format!("{dst_start} {src_start} {range_len}");
+                    src_start += range_len;
+                    line
+                })
+                .collect::<Vec<_>>()
+        })
+        .prop_shuffle();
+    (
+        prop::collection::vec(0..1000u64, 1..10),
+        prop::collection::vec(map, 7),
+    )
+        .prop_map(|(seeds, maps)| {
+            let mut output = format!(
+                "seeds: {}",
+                seeds
+                    .iter()
+                    .map(u64::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            );
+            for (name, lines) in MAPS.iter().zip(maps) {
+                // empty maps are dropped, as the parser needs at least one line
+                if !lines.is_empty() {
+                    output.push_str(&format!("\n\n{name} map:\n{}", lines.join("\n")));
+                }
+            }
+            output
+        })
+}
+
+fn spring_row() -> impl Strategy<Value = String> {
+    let spring = prop::sample::select(vec!['.', '#', '?', '?']);
+    (
+        prop::collection::vec(spring, 1..16),
+        prop::collection::vec(1..4usize, 0..5),
+    )
+        .prop_map(|(springs, groups)| {
+            format!(
+                "{} {}",
+                String::from_iter(springs),
+                groups
+                    .iter()
+                    .map(usize::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            )
+        })
+}
+
+proptest! {
+    #[test]
+    fn day3_part1(input in schematic()) {
+        prop_assert_eq!(
+            day3::part1::solve_with::<i64>(&input, Default::default()).unwrap(),
+            reference::day3::part1(&input)
+        );
+    }
+
+    #[test]
+    fn day3_part2(input in schematic()) {
+        prop_assert_eq!(
+            day3::part2::solve_with::<i64>(&input, Default::default()).unwrap(),
+            reference::day3::part2(&input)
+        );
+    }
+
+    #[test]
+    fn day4_part1(input in scratchcards()) {
+        prop_assert_eq!(day4::part1::solve(&input).unwrap(), reference::day4::part1(&input));
+    }
+
+    #[test]
+    fn day4_part2(input in scratchcards()) {
+        prop_assert_eq!(day4::part2::solve(&input).unwrap(), reference::day4::part2(&input));
+    }
+
+    #[test]
+    fn day5_part1(input in almanac()) {
+        prop_assert_eq!(day5::part1::solve(&input).unwrap(), reference::day5::part1(&input));
+    }
+
+    #[test]
+    fn day12_arrangements(line in spring_row()) {
+        let row = day12::Row::parse(&line).unwrap();
+        prop_assert_eq!(row.arrangements(), Some(row.enumerate().unwrap().len() as u64));
+    }
+}